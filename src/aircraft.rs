@@ -0,0 +1,250 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::communication::V1Communication;
+use crate::full::{Updatable, V1PathValue};
+use crate::helper_functions::{
+    apply_update_values, get_path, json_as_optional_string, json_leaves, Path,
+};
+use crate::notification::V1Notification;
+use crate::{SignalKGetError, V1Navigation, V1UpdateInfo, V1UpdateType};
+
+/// An object describing an individual aircraft. It should be an object
+/// in aircraft, named using MMSI or a UUID
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Aircraft {
+    /// MMSI number of the aircraft, if available.
+    pub mmsi: Option<String>,
+
+    /// URL based identity of the aircraft, if available.
+    pub url: Option<String>,
+
+    /// A unique Signal K flavoured maritime resource identifier, assigned by the server.
+    pub uuid: Option<String>,
+
+    /// The common name of the aircraft
+    pub name: Option<String>,
+
+    /// The country of registration, or flag state of the aircraft
+    pub flag: Option<String>,
+
+    /// The home port of the aircraft
+    pub port: Option<String>,
+
+    /// The home base of the aircraft
+    pub base: Option<String>,
+
+    /// Position, course and speed of the aircraft
+    pub navigation: Option<V1Navigation>,
+    pub communication: Option<V1Communication>,
+    /// Notifications currently raised for the aircraft
+    pub notifications: Option<V1Notification>,
+}
+
+impl Path<f64> for V1Aircraft {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1Aircraft::get_path({:?})", path);
+        match path[0] {
+            "mmsi" => Err(SignalKGetError::WrongDataType),
+            "url" => Err(SignalKGetError::WrongDataType),
+            "uuid" => Err(SignalKGetError::WrongDataType),
+            "name" => Err(SignalKGetError::WrongDataType),
+            "flag" => Err(SignalKGetError::WrongDataType),
+            "port" => Err(SignalKGetError::WrongDataType),
+            "base" => Err(SignalKGetError::WrongDataType),
+            "navigation" => get_path(path, &self.navigation.as_ref()),
+            "communication" => Err(SignalKGetError::WrongDataType),
+            "notifications" => get_path(path, &self.notifications.as_ref()),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl Updatable for V1Aircraft {
+    fn apply_update(&mut self, update: &V1UpdateType) {
+        self.apply_update_with(update, &mut |_, _, _| true);
+    }
+
+    fn id(&self) -> String {
+        if let Some(ref id) = self.mmsi {
+            return id.clone();
+        }
+        if let Some(ref id) = self.uuid {
+            return id.clone();
+        }
+        "".into()
+    }
+
+    fn type_name(&self) -> String {
+        "V1Aircraft".to_string()
+    }
+}
+
+impl V1Aircraft {
+    pub fn builder() -> V1AircraftBuilder {
+        V1AircraftBuilder::default()
+    }
+
+    /// Create an aircraft from its id in a context, e.g. `urn:mrn:imo:mmsi:111232511`
+    pub fn new_with_id(id: &str) -> Self {
+        match id.split(':').collect::<Vec<&str>>()[..] {
+            ["urn", "mrn", "imo", "mmsi", mmsi] => Self::builder().mmsi(mmsi.to_string()).build(),
+            ["urn", "mrn", "signalk", "uuid", uuid] => {
+                Self::builder().uuid(uuid.to_string()).build()
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Apply an update, asking `accept` for each value if it should replace
+    /// the current value of its path
+    pub fn apply_update_with<F>(&mut self, update: &V1UpdateType, accept: &mut F)
+    where
+        F: FnMut(&str, &serde_json::Value, &V1UpdateInfo) -> bool,
+    {
+        apply_update_values(self, update, accept, V1Aircraft::update);
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "mmsi" | "url" | "uuid" | "name" | "flag" | "port" | "base" if info.keeps_value() => {}
+            "mmsi" => self.mmsi = json_as_optional_string(value),
+            "url" => self.url = json_as_optional_string(value),
+            "uuid" => self.uuid = json_as_optional_string(value),
+            "name" => self.name = json_as_optional_string(value),
+            "flag" => self.flag = json_as_optional_string(value),
+            "port" => self.port = json_as_optional_string(value),
+            "base" => self.base = json_as_optional_string(value),
+            "navigation" => {
+                if self.navigation.is_none() {
+                    self.navigation = Some(V1Navigation::default());
+                }
+                if let Some(ref mut navigation) = self.navigation {
+                    path.remove(0);
+                    navigation.update(path, value, info);
+                }
+            }
+            "communication" => {
+                if self.communication.is_none() {
+                    self.communication = Some(V1Communication::default());
+                }
+                if let Some(ref mut communication) = self.communication {
+                    path.remove(0);
+                    communication.update(path, value, info);
+                }
+            }
+            "notifications" => {
+                if self.notifications.is_none() {
+                    self.notifications = Some(V1Notification::default());
+                }
+                if let Some(ref mut notifications) = self.notifications {
+                    path.remove(0);
+                    notifications.update(path, value, info);
+                    if notifications.is_empty() {
+                        self.notifications = None;
+                    }
+                }
+            }
+            "" => {
+                if let serde_json::Value::Object(ref map) = value {
+                    for (k, v) in map.iter() {
+                        let mut path = vec![k.as_str()];
+                        self.update(&mut path, v, info);
+                    }
+                }
+            }
+            &_ => {
+                log::warn!(
+                    "V1Aircraft: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+
+    /// Iterate over every populated leaf in this aircraft
+    pub fn leaves(&self) -> std::vec::IntoIter<V1PathValue> {
+        json_leaves(self)
+    }
+}
+
+#[derive(Default)]
+pub struct V1AircraftBuilder {
+    mmsi: Option<String>,
+    url: Option<String>,
+    uuid: Option<String>,
+    name: Option<String>,
+    flag: Option<String>,
+    port: Option<String>,
+    base: Option<String>,
+    navigation: Option<V1Navigation>,
+    communication: Option<V1Communication>,
+    notifications: Option<V1Notification>,
+}
+
+impl V1AircraftBuilder {
+    pub fn mmsi(mut self, value: String) -> V1AircraftBuilder {
+        self.mmsi = Some(value);
+        self
+    }
+    pub fn url(mut self, value: String) -> V1AircraftBuilder {
+        self.url = Some(value);
+        self
+    }
+    pub fn uuid(mut self, value: String) -> V1AircraftBuilder {
+        self.uuid = Some(value);
+        self
+    }
+    pub fn name(mut self, value: String) -> V1AircraftBuilder {
+        self.name = Some(value);
+        self
+    }
+    pub fn flag(mut self, value: String) -> V1AircraftBuilder {
+        self.flag = Some(value);
+        self
+    }
+    pub fn port(mut self, value: String) -> V1AircraftBuilder {
+        self.port = Some(value);
+        self
+    }
+    pub fn base(mut self, value: String) -> V1AircraftBuilder {
+        self.base = Some(value);
+        self
+    }
+    pub fn navigation(mut self, value: V1Navigation) -> V1AircraftBuilder {
+        self.navigation = Some(value);
+        self
+    }
+    pub fn communication(mut self, value: V1Communication) -> V1AircraftBuilder {
+        self.communication = Some(value);
+        self
+    }
+    pub fn notifications(mut self, value: V1Notification) -> V1AircraftBuilder {
+        self.notifications = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Aircraft {
+        V1Aircraft {
+            mmsi: self.mmsi,
+            url: self.url,
+            uuid: self.uuid,
+            name: self.name,
+            flag: self.flag,
+            port: self.port,
+            base: self.base,
+            navigation: self.navigation,
+            communication: self.communication,
+            notifications: self.notifications,
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::communication::V1Communication;
+use crate::definitions::{update_value, UpdatableValue};
+use crate::design::V1Design;
+use crate::environment::V1Environment;
+use crate::full::{Updatable, V1PathValue};
+use crate::helper_functions::{
+    apply_update_values, get_f64_value_for_path, get_path, json_as_optional_string, json_leaves,
+    Path,
+};
+use crate::notification::V1Notification;
+use crate::{SignalKGetError, V1CommonValueFields, V1Navigation, V1UpdateInfo, V1UpdateType};
+
+/// An object describing an individual aid to navigation. It should be an object
+/// in aton, named using MMSI or a UUID
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Aton {
+    /// MMSI number of the aid to navigation, if available.
+    pub mmsi: Option<String>,
+
+    /// URL based identity of the aid to navigation, if available.
+    pub url: Option<String>,
+
+    /// A unique Signal K flavoured maritime resource identifier, assigned by the server.
+    pub uuid: Option<String>,
+
+    /// The common name of the aid to navigation
+    pub name: Option<String>,
+
+    /// The type of the aid to navigation, as reported by AIS
+    pub aton_type: Option<V1AtonType>,
+
+    /// Set when the aid to navigation only exists as an AIS message
+    #[serde(rename = "virtual")]
+    pub virtual_: Option<bool>,
+
+    /// Set when a floating aid to navigation is off its charted position
+    pub off_position: Option<bool>,
+
+    /// Position of the aid to navigation
+    pub navigation: Option<V1Navigation>,
+    pub communication: Option<V1Communication>,
+    /// Environmental data measured at the aid to navigation
+    pub environment: Option<V1Environment>,
+    pub design: Option<V1Design>,
+    /// Notifications currently raised for the aid to navigation
+    pub notifications: Option<V1Notification>,
+}
+
+impl Path<f64> for V1Aton {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1Aton::get_path({:?})", path);
+        match path[0] {
+            "mmsi" => Err(SignalKGetError::WrongDataType),
+            "url" => Err(SignalKGetError::WrongDataType),
+            "uuid" => Err(SignalKGetError::WrongDataType),
+            "name" => Err(SignalKGetError::WrongDataType),
+            "atonType" => Err(SignalKGetError::WrongDataType),
+            "virtual" => Err(SignalKGetError::WrongDataType),
+            "offPosition" => Err(SignalKGetError::WrongDataType),
+            "navigation" => get_path(path, &self.navigation.as_ref()),
+            "communication" => Err(SignalKGetError::WrongDataType),
+            "environment" => get_path(path, &self.environment.as_ref()),
+            "design" => get_f64_value_for_path(&mut path.to_vec(), &self.design),
+            "notifications" => get_path(path, &self.notifications.as_ref()),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl Updatable for V1Aton {
+    fn apply_update(&mut self, update: &V1UpdateType) {
+        self.apply_update_with(update, &mut |_, _, _| true);
+    }
+
+    fn id(&self) -> String {
+        if let Some(ref id) = self.mmsi {
+            return id.clone();
+        }
+        if let Some(ref id) = self.uuid {
+            return id.clone();
+        }
+        "".into()
+    }
+
+    fn type_name(&self) -> String {
+        "V1Aton".to_string()
+    }
+}
+
+impl V1Aton {
+    pub fn builder() -> V1AtonBuilder {
+        V1AtonBuilder::default()
+    }
+
+    /// Create an aid to navigation from its id in a context, e.g. `urn:mrn:imo:mmsi:993672085`
+    pub fn new_with_id(id: &str) -> Self {
+        match id.split(':').collect::<Vec<&str>>()[..] {
+            ["urn", "mrn", "imo", "mmsi", mmsi] => Self::builder().mmsi(mmsi.to_string()).build(),
+            ["urn", "mrn", "signalk", "uuid", uuid] => {
+                Self::builder().uuid(uuid.to_string()).build()
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// Apply an update, asking `accept` for each value if it should replace
+    /// the current value of its path
+    pub fn apply_update_with<F>(&mut self, update: &V1UpdateType, accept: &mut F)
+    where
+        F: FnMut(&str, &serde_json::Value, &V1UpdateInfo) -> bool,
+    {
+        apply_update_values(self, update, accept, V1Aton::update);
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "mmsi" | "url" | "uuid" | "name" | "virtual" | "offPosition" if info.keeps_value() => {}
+            "mmsi" => self.mmsi = json_as_optional_string(value),
+            "url" => self.url = json_as_optional_string(value),
+            "uuid" => self.uuid = json_as_optional_string(value),
+            "name" => self.name = json_as_optional_string(value),
+            "atonType" => update_value(&mut self.aton_type, value, info),
+            "virtual" => self.virtual_ = value.as_bool(),
+            "offPosition" => self.off_position = value.as_bool(),
+            "navigation" => {
+                if self.navigation.is_none() {
+                    self.navigation = Some(V1Navigation::default());
+                }
+                if let Some(ref mut navigation) = self.navigation {
+                    path.remove(0);
+                    navigation.update(path, value, info);
+                }
+            }
+            "communication" => {
+                if self.communication.is_none() {
+                    self.communication = Some(V1Communication::default());
+                }
+                if let Some(ref mut communication) = self.communication {
+                    path.remove(0);
+                    communication.update(path, value, info);
+                }
+            }
+            "environment" => {
+                if self.environment.is_none() {
+                    self.environment = Some(V1Environment::default());
+                }
+                if let Some(ref mut environment) = self.environment {
+                    path.remove(0);
+                    environment.update(path, value, info);
+                }
+            }
+            "design" => {
+                if self.design.is_none() {
+                    self.design = Some(V1Design::default());
+                }
+                if let Some(ref mut design) = self.design {
+                    path.remove(0);
+                    design.update(path, value, info);
+                }
+            }
+            "notifications" => {
+                if self.notifications.is_none() {
+                    self.notifications = Some(V1Notification::default());
+                }
+                if let Some(ref mut notifications) = self.notifications {
+                    path.remove(0);
+                    notifications.update(path, value, info);
+                    if notifications.is_empty() {
+                        self.notifications = None;
+                    }
+                }
+            }
+            "" => {
+                if let serde_json::Value::Object(ref map) = value {
+                    for (k, v) in map.iter() {
+                        let mut path = vec![k.as_str()];
+                        self.update(&mut path, v, info);
+                    }
+                }
+            }
+            &_ => {
+                log::warn!("V1Aton: Unknown update pattern: {:?}::{:?}", path, value);
+            }
+        }
+    }
+
+    /// Iterate over every populated leaf in this aid to navigation
+    pub fn leaves(&self) -> std::vec::IntoIter<V1PathValue> {
+        json_leaves(self)
+    }
+}
+
+#[derive(Default)]
+pub struct V1AtonBuilder {
+    mmsi: Option<String>,
+    url: Option<String>,
+    uuid: Option<String>,
+    name: Option<String>,
+    aton_type: Option<V1AtonType>,
+    virtual_: Option<bool>,
+    off_position: Option<bool>,
+    navigation: Option<V1Navigation>,
+    communication: Option<V1Communication>,
+    environment: Option<V1Environment>,
+    design: Option<V1Design>,
+    notifications: Option<V1Notification>,
+}
+
+impl V1AtonBuilder {
+    pub fn mmsi(mut self, value: String) -> V1AtonBuilder {
+        self.mmsi = Some(value);
+        self
+    }
+    pub fn url(mut self, value: String) -> V1AtonBuilder {
+        self.url = Some(value);
+        self
+    }
+    pub fn uuid(mut self, value: String) -> V1AtonBuilder {
+        self.uuid = Some(value);
+        self
+    }
+    pub fn name(mut self, value: String) -> V1AtonBuilder {
+        self.name = Some(value);
+        self
+    }
+    pub fn aton_type(mut self, value: V1AtonType) -> V1AtonBuilder {
+        self.aton_type = Some(value);
+        self
+    }
+    pub fn virtual_(mut self, value: bool) -> V1AtonBuilder {
+        self.virtual_ = Some(value);
+        self
+    }
+    pub fn off_position(mut self, value: bool) -> V1AtonBuilder {
+        self.off_position = Some(value);
+        self
+    }
+    pub fn navigation(mut self, value: V1Navigation) -> V1AtonBuilder {
+        self.navigation = Some(value);
+        self
+    }
+    pub fn communication(mut self, value: V1Communication) -> V1AtonBuilder {
+        self.communication = Some(value);
+        self
+    }
+    pub fn environment(mut self, value: V1Environment) -> V1AtonBuilder {
+        self.environment = Some(value);
+        self
+    }
+    pub fn design(mut self, value: V1Design) -> V1AtonBuilder {
+        self.design = Some(value);
+        self
+    }
+    pub fn notifications(mut self, value: V1Notification) -> V1AtonBuilder {
+        self.notifications = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Aton {
+        V1Aton {
+            mmsi: self.mmsi,
+            url: self.url,
+            uuid: self.uuid,
+            name: self.name,
+            aton_type: self.aton_type,
+            virtual_: self.virtual_,
+            off_position: self.off_position,
+            navigation: self.navigation,
+            communication: self.communication,
+            environment: self.environment,
+            design: self.design,
+            notifications: self.notifications,
+        }
+    }
+}
+
+/// The AIS type of an aid to navigation
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1AtonType {
+    pub value: Option<V1AtonTypeValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+}
+
+impl UpdatableValue for V1AtonType {
+    fn set_value(&mut self, value: &serde_json::Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, _value: &serde_json::Value, _info: &V1UpdateInfo) {
+        // The type of an aid to navigation only has one source
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1AtonTypeValue {
+    pub id: i64,
+    pub name: String,
+}
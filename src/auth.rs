@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+/// A websocket authentication message: a login, logout or validate request,
+/// or the response from the server to one of them
+///
+/// Exactly one of `login`, `logout` and `validate` must be present.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(try_from = "V1AuthFields")]
+pub struct V1Auth {
+    pub request_id: String,
+    /// The state of the request, set in responses
+    pub state: Option<V1AuthState>,
+    /// The HTTP style status code of the response, e.g. 200 or 401
+    #[serde(rename = "result", alias = "statusCode")]
+    pub status_code: Option<i64>,
+    pub login: Option<V1AuthLogin>,
+    pub logout: Option<V1AuthToken>,
+    pub validate: Option<V1AuthToken>,
+}
+
+impl V1Auth {
+    pub fn builder() -> V1AuthBuilder {
+        V1AuthBuilder::default()
+    }
+
+    /// A login request with the username and password of the user
+    pub fn login_request(request_id: String, username: String, password: String) -> Self {
+        Self::builder()
+            .request_id(request_id)
+            .login(V1AuthLogin::Credentials(V1AuthCredentials::new(
+                username, password,
+            )))
+            .build()
+    }
+
+    /// A logout request for `token`
+    pub fn logout_request(request_id: String, token: String) -> Self {
+        Self::builder()
+            .request_id(request_id)
+            .logout(V1AuthToken::builder().token(token).build())
+            .build()
+    }
+
+    /// A request to check that `token` is still valid, and to renew it
+    pub fn validate_request(request_id: String, token: String) -> Self {
+        Self::builder()
+            .request_id(request_id)
+            .validate(V1AuthToken::builder().token(token).build())
+            .build()
+    }
+
+    /// The token given to the client in a login or validate response
+    pub fn token(&self) -> Option<&V1AuthToken> {
+        match self.login {
+            Some(V1AuthLogin::Token(ref token)) => Some(token),
+            _ if self.state.is_some() => self.validate.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V1AuthFields {
+    request_id: String,
+    state: Option<V1AuthState>,
+    #[serde(rename = "result", alias = "statusCode")]
+    status_code: Option<i64>,
+    login: Option<V1AuthLogin>,
+    logout: Option<V1AuthToken>,
+    validate: Option<V1AuthToken>,
+}
+
+impl TryFrom<V1AuthFields> for V1Auth {
+    type Error = String;
+
+    fn try_from(fields: V1AuthFields) -> Result<Self, Self::Error> {
+        let messages = [
+            fields.login.is_some(),
+            fields.logout.is_some(),
+            fields.validate.is_some(),
+        ];
+        if messages.iter().filter(|m| **m).count() != 1 {
+            return Err("expected exactly one of login, logout or validate".to_string());
+        }
+        Ok(V1Auth {
+            request_id: fields.request_id,
+            state: fields.state,
+            status_code: fields.status_code,
+            login: fields.login,
+            logout: fields.logout,
+            validate: fields.validate,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct V1AuthBuilder {
+    request_id: String,
+    state: Option<V1AuthState>,
+    status_code: Option<i64>,
+    login: Option<V1AuthLogin>,
+    logout: Option<V1AuthToken>,
+    validate: Option<V1AuthToken>,
+}
+
+impl V1AuthBuilder {
+    pub fn request_id(mut self, value: String) -> V1AuthBuilder {
+        self.request_id = value;
+        self
+    }
+    pub fn state(mut self, value: V1AuthState) -> V1AuthBuilder {
+        self.state = Some(value);
+        self
+    }
+    pub fn status_code(mut self, value: i64) -> V1AuthBuilder {
+        self.status_code = Some(value);
+        self
+    }
+    pub fn login(mut self, value: V1AuthLogin) -> V1AuthBuilder {
+        self.login = Some(value);
+        self
+    }
+    pub fn logout(mut self, value: V1AuthToken) -> V1AuthBuilder {
+        self.logout = Some(value);
+        self
+    }
+    pub fn validate(mut self, value: V1AuthToken) -> V1AuthBuilder {
+        self.validate = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Auth {
+        V1Auth {
+            request_id: self.request_id,
+            state: self.state,
+            status_code: self.status_code,
+            login: self.login,
+            logout: self.logout,
+            validate: self.validate,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum V1AuthState {
+    Pending,
+    Completed,
+}
+
+/// The login part of an authentication message, the credentials in a
+/// request or the token in a response
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(untagged)]
+pub enum V1AuthLogin {
+    Credentials(V1AuthCredentials),
+    Token(V1AuthToken),
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1AuthCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl V1AuthCredentials {
+    pub fn new(username: String, password: String) -> Self {
+        Self { username, password }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1AuthToken {
+    pub token: String,
+    /// The number of seconds the token is valid, set in responses
+    pub time_to_live: Option<i64>,
+}
+
+impl V1AuthToken {
+    pub fn builder() -> V1AuthTokenBuilder {
+        V1AuthTokenBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct V1AuthTokenBuilder {
+    token: String,
+    time_to_live: Option<i64>,
+}
+
+impl V1AuthTokenBuilder {
+    pub fn token(mut self, value: String) -> V1AuthTokenBuilder {
+        self.token = value;
+        self
+    }
+    pub fn time_to_live(mut self, value: i64) -> V1AuthTokenBuilder {
+        self.time_to_live = Some(value);
+        self
+    }
+    pub fn build(self) -> V1AuthToken {
+        V1AuthToken {
+            token: self.token,
+            time_to_live: self.time_to_live,
+        }
+    }
+}
+
+#[cfg(test)]
+mod auth_tests {
+    use serde_json::json;
+
+    use crate::auth::{V1Auth, V1AuthLogin, V1AuthState, V1AuthToken};
+
+    #[test]
+    fn login_request_round_trip() {
+        let request = V1Auth::login_request("1234".into(), "john_doe".into(), "secret".into());
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["login"],
+            json!({"username": "john_doe", "password": "secret"})
+        );
+        let parsed: V1Auth = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, request);
+        assert_eq!(parsed.token(), None);
+    }
+
+    #[test]
+    fn login_response_token() {
+        let response: V1Auth = serde_json::from_value(json!({
+            "requestId": "1234",
+            "state": "COMPLETED",
+            "statusCode": 200,
+            "login": {"token": "abc", "timeToLive": 3600}
+        }))
+        .unwrap();
+        let token = V1AuthToken::builder()
+            .token("abc".into())
+            .time_to_live(3600)
+            .build();
+        assert_eq!(
+            response,
+            V1Auth::builder()
+                .request_id("1234".into())
+                .state(V1AuthState::Completed)
+                .status_code(200)
+                .login(V1AuthLogin::Token(token.clone()))
+                .build()
+        );
+        assert_eq!(response.token(), Some(&token));
+    }
+
+    #[test]
+    fn validate_request_and_response() {
+        let request = V1Auth::validate_request("1234".into(), "abc".into());
+        assert_eq!(request.token(), None);
+        let response = V1Auth::builder()
+            .request_id("1234".into())
+            .state(V1AuthState::Completed)
+            .status_code(200)
+            .validate(V1AuthToken::builder().token("def".into()).build())
+            .build();
+        assert_eq!(response.token().unwrap().token, "def".to_string());
+    }
+
+    #[test]
+    fn only_one_message_per_auth() {
+        let result: Result<V1Auth, _> = serde_json::from_value(json!({
+            "requestId": "1234",
+            "state": "COMPLETED",
+            "result": 401
+        }));
+        assert!(result.is_err());
+        let result: Result<V1Auth, _> = serde_json::from_value(json!({
+            "requestId": "1234",
+            "logout": {"token": "abc"},
+            "validate": {"token": "abc"}
+        }));
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{helper_functions, SignalKGetError};
+use crate::{helper_functions, SignalKGetError, V1UpdateInfo};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -18,14 +18,22 @@ impl V1Communication {
         V1CommunicationBuilder::default()
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if info.keeps_value() {
+            return;
+        }
         if path.is_empty() {
             log::warn!("Complex value object {:?}", value);
             if let serde_json::Value::Object(ref map) = value {
                 for (k, v) in map.iter() {
                     log::debug!(" key: {:?} value: {:?}", k, v);
                     let mut path = vec![k.as_str()];
-                    self.update(&mut path, v);
+                    self.update(&mut path, v, info);
                 }
             }
         } else {
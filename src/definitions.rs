@@ -1,6 +1,9 @@
 use crate::definitions::V2NumberValue::Int;
+use crate::delta::V1UpdateInfo;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -9,9 +12,11 @@ pub struct V1CommonValueFields {
     pub timestamp: String,
     #[serde(rename = "$source")]
     pub source: String,
+    #[serde(rename = "source")]
+    pub def_source: Option<Box<V1DefSource>>,
     #[serde(rename = "_attr")]
-    pub attr: Option<V1Attr>,
-    pub meta: Option<V1Meta>,
+    pub attr: Option<Box<V1Attr>>,
+    pub meta: Option<Box<V1Meta>>,
     pub pgn: Option<i64>,
     pub sentence: Option<String>,
 }
@@ -20,12 +25,41 @@ impl V1CommonValueFields {
     pub fn builder() -> V1CommonValueFieldsBuilder {
         V1CommonValueFieldsBuilder::default()
     }
+
+    /// Store the timestamp, sources and meta data that are set in `info`
+    pub fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if let Some(ref timestamp) = info.timestamp {
+            self.timestamp = timestamp.clone();
+        }
+        if let Some(ref source) = info.ref_source {
+            self.source = source.clone();
+        }
+        if let Some(ref def_source) = info.source {
+            self.pgn = def_source.pgn.map(i64::from);
+            self.sentence = def_source.sentence.clone();
+            self.def_source = Some(Box::new(def_source.clone()));
+        }
+        if let Some(ref meta) = info.meta {
+            self.meta = Some(Box::new(meta.clone()));
+        }
+    }
+
+    /// The value as reported by the current source
+    pub fn source_value<T>(&self, value: Option<T>) -> V1SourceValue<T> {
+        V1SourceValue {
+            value,
+            timestamp: non_empty(&self.timestamp),
+            pgn: self.pgn.map(|pgn| pgn as f64),
+            sentence: self.sentence.clone(),
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct V1CommonValueFieldsBuilder {
     timestamp: String,
     source: String,
+    def_source: Option<V1DefSource>,
     attr: Option<V1Attr>,
     meta: Option<V1Meta>,
     pgn: Option<i64>,
@@ -41,6 +75,10 @@ impl V1CommonValueFieldsBuilder {
         self.source = value;
         self
     }
+    pub fn def_source(mut self, value: V1DefSource) -> V1CommonValueFieldsBuilder {
+        self.def_source = Some(value);
+        self
+    }
     pub fn attr(mut self, value: V1Attr) -> V1CommonValueFieldsBuilder {
         self.attr = Some(value);
         self
@@ -61,8 +99,9 @@ impl V1CommonValueFieldsBuilder {
         V1CommonValueFields {
             timestamp: self.timestamp,
             source: self.source,
-            attr: self.attr,
-            meta: self.meta,
+            def_source: self.def_source.map(Box::new),
+            attr: self.attr.map(Box::new),
+            meta: self.meta.map(Box::new),
             pgn: self.pgn,
             sentence: self.sentence,
         }
@@ -73,6 +112,100 @@ pub trait F64Compatible {
     fn as_f64(&self) -> Option<f64>;
 }
 
+/// A leaf value in the data model that can be written by a delta update
+pub trait UpdatableValue: Default {
+    fn set_value(&mut self, value: &Value);
+    /// Store the timestamp, sources and meta data that are set in `info`
+    fn set_update_info(&mut self, info: &V1UpdateInfo);
+    /// Keep the value from the `$source` in `info` in the `values` map
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo);
+}
+
+/// Write a value from a delta into a leaf, creating the leaf when missing
+///
+/// A `null` value without meta data clears the leaf. The timestamp, sources
+/// and meta data of the update are stored with the value, anything the update
+/// does not carry is kept from earlier updates. When the update is only a
+/// source value it is added to the `values` of an existing leaf. Meta data
+/// without a value is attached to the leaf as it is.
+pub fn update_value<T: UpdatableValue>(slot: &mut Option<T>, value: &Value, info: &V1UpdateInfo) {
+    if info.meta_only {
+        slot.get_or_insert_with(T::default).set_update_info(info);
+        return;
+    }
+    if info.source_value_only {
+        if let Some(leaf) = slot {
+            leaf.add_source_value(value, info);
+        }
+        return;
+    }
+    if value.is_null() && info.meta.is_none() {
+        *slot = None;
+        return;
+    }
+    let leaf = slot.get_or_insert_with(T::default);
+    leaf.add_source_value(value, info);
+    leaf.set_value(value);
+    leaf.set_update_info(info);
+}
+
+/// The value of a path as reported by one source
+///
+/// When more than one source reports the same path all of them are kept in a
+/// `values` object keyed by `$source`, next to the current value of the path.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct V1SourceValue<T = Value> {
+    pub value: Option<T>,
+    pub timestamp: Option<String>,
+    pub pgn: Option<f64>,
+    pub sentence: Option<String>,
+}
+
+impl<T: DeserializeOwned> V1SourceValue<T> {
+    pub fn from_update(value: &Value, info: &V1UpdateInfo) -> Self {
+        let def_source = info.source.as_ref();
+        Self {
+            value: serde_json::from_value(value.clone()).ok(),
+            timestamp: info.timestamp.clone(),
+            pgn: def_source.and_then(|source| source.pgn).map(f64::from),
+            sentence: def_source.and_then(|source| source.sentence.clone()),
+        }
+    }
+}
+
+/// Add the value of an update to the `values` map of a leaf
+///
+/// The map is created when a second source reports the path, the value from
+/// the source that was stored until then, `current`, is added to it as well.
+pub fn add_source_value<T: DeserializeOwned>(
+    values: &mut Option<HashMap<String, V1SourceValue<T>>>,
+    current_source: &str,
+    current: impl FnOnce() -> V1SourceValue<T>,
+    value: &Value,
+    info: &V1UpdateInfo,
+) {
+    let Some(ref source) = info.ref_source else {
+        return;
+    };
+    if values.is_none() {
+        if current_source.is_empty() || current_source == source {
+            return;
+        }
+        *values = Some(HashMap::from([(current_source.to_string(), current())]));
+    }
+    if let Some(ref mut values) = values {
+        values.insert(source.clone(), V1SourceValue::from_update(value, info));
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 #[serde(untagged)]
 pub enum V2NumberValue {
@@ -86,13 +219,7 @@ impl V2NumberValue {
         if value.is_null() {
             None
         } else {
-            let ship_type_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(ship_type_value) = ship_type_result {
-                Some(ship_type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -106,11 +233,64 @@ impl F64Compatible for V2NumberValue {
     }
 }
 
+impl UpdatableValue for V2NumberValue {
+    fn set_value(&mut self, value: &Value) {
+        match self {
+            V2NumberValue::ExpandedFloat(ref mut expanded) => expanded.value = value.as_f64(),
+            _ => {
+                *self = if let Some(int_value) = value.as_i64() {
+                    Int(int_value)
+                } else if let Some(float_value) = value.as_f64() {
+                    V2NumberValue::Float(float_value)
+                } else {
+                    V2NumberValue::ExpandedFloat(V2NumberExpandedFloatValue::default())
+                }
+            }
+        }
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if info.is_empty() {
+            return;
+        }
+        if !matches!(self, V2NumberValue::ExpandedFloat(_)) {
+            *self = V2NumberValue::ExpandedFloat(V2NumberExpandedFloatValue {
+                value: self.as_f64(),
+                common_value_fields: None,
+                values: None,
+            });
+        }
+        if let V2NumberValue::ExpandedFloat(ref mut expanded) = self {
+            expanded
+                .common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if info.ref_source.is_none() {
+            return;
+        }
+        let current_value = self.as_f64();
+        if let V2NumberValue::ExpandedFloat(ref mut expanded) = self {
+            if let Some(ref common) = expanded.common_value_fields {
+                add_source_value(
+                    &mut expanded.values,
+                    &common.source,
+                    || common.source_value(current_value),
+                    value,
+                    info,
+                );
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct V2NumberExpandedFloatValue {
     pub value: Option<f64>,
     #[serde(flatten)]
     pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<f64>>>,
 }
 
 impl Default for V2NumberValue {
@@ -125,9 +305,12 @@ pub struct V1NumberValue {
     pub timestamp: String,
     #[serde(rename = "$source")]
     pub source: String,
+    #[serde(rename = "source")]
+    pub def_source: Option<Box<V1DefSource>>,
     pub pgn: Option<f64>,
     pub sentence: Option<String>,
-    // TODO: Add support for meta values
+    pub meta: Option<Box<V1Meta>>,
+    pub values: Option<HashMap<String, V1SourceValue<f64>>>,
 }
 
 impl V1NumberValue {
@@ -139,13 +322,7 @@ impl V1NumberValue {
         if value.is_null() {
             None
         } else {
-            let type_result: Result<V1NumberValue, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(type_value) = type_result {
-                Some(type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -156,13 +333,52 @@ impl F64Compatible for V1NumberValue {
     }
 }
 
+impl UpdatableValue for V1NumberValue {
+    fn set_value(&mut self, value: &Value) {
+        self.value = value.as_f64();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if let Some(ref timestamp) = info.timestamp {
+            self.timestamp = timestamp.clone();
+        }
+        if let Some(ref source) = info.ref_source {
+            self.source = source.clone();
+        }
+        if let Some(ref def_source) = info.source {
+            self.pgn = def_source.pgn.map(f64::from);
+            self.sentence = def_source.sentence.clone();
+            self.def_source = Some(Box::new(def_source.clone()));
+        }
+        if let Some(ref meta) = info.meta {
+            self.meta = Some(Box::new(meta.clone()));
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        add_source_value(
+            &mut self.values,
+            &self.source,
+            || V1SourceValue {
+                value: self.value,
+                timestamp: non_empty(&self.timestamp),
+                pgn: self.pgn,
+                sentence: self.sentence.clone(),
+            },
+            value,
+            info,
+        );
+    }
+}
+
 #[derive(Default)]
 pub struct V1NumberValueBuilder {
     pub value: Option<f64>,
     pub timestamp: String,
     pub source: String,
+    pub def_source: Option<Box<V1DefSource>>,
     pub pgn: Option<f64>,
     pub sentence: Option<String>,
+    pub meta: Option<Box<V1Meta>>,
+    pub values: Option<HashMap<String, V1SourceValue<f64>>>,
 }
 
 impl V1NumberValueBuilder {
@@ -186,6 +402,10 @@ impl V1NumberValueBuilder {
         self.source = source;
         self
     }
+    pub fn def_source(mut self, def_source: V1DefSource) -> V1NumberValueBuilder {
+        self.def_source = Some(Box::new(def_source));
+        self
+    }
     pub fn pgn(mut self, pgn: f64) -> V1NumberValueBuilder {
         self.pgn = Some(pgn);
         self
@@ -194,13 +414,30 @@ impl V1NumberValueBuilder {
         self.sentence = Some(sentence);
         self
     }
+    pub fn meta(mut self, meta: V1Meta) -> V1NumberValueBuilder {
+        self.meta = Some(Box::new(meta));
+        self
+    }
+    pub fn add_source_value(
+        mut self,
+        source: String,
+        value: V1SourceValue<f64>,
+    ) -> V1NumberValueBuilder {
+        self.values
+            .get_or_insert_with(HashMap::new)
+            .insert(source, value);
+        self
+    }
     pub fn build(self) -> V1NumberValue {
         V1NumberValue {
             value: self.value,
             timestamp: self.timestamp,
             source: self.source,
+            def_source: self.def_source,
             pgn: self.pgn,
             sentence: self.sentence,
+            meta: self.meta,
+            values: self.values,
         }
     }
 }
@@ -208,6 +445,7 @@ impl V1NumberValueBuilder {
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1Meta {
+    #[serde(default)]
     pub description: String,
     pub display_name: Option<String>,
     pub long_name: Option<String>,
@@ -230,6 +468,31 @@ impl V1Meta {
     pub fn builder() -> V1MetaBuilder {
         V1MetaBuilder::default()
     }
+
+    /// The zone a value falls in, the first matching zone in the list wins
+    pub fn zone_for(&self, value: f64) -> Option<&V1MetaZone> {
+        self.zones
+            .as_ref()?
+            .iter()
+            .find(|zone| zone.contains(value))
+    }
+
+    /// The methods to notify with for a zone state
+    ///
+    /// Uses the method list of the state when given, otherwise `visual` for
+    /// `alert` and `warn` and `sound` and `visual` for `alarm` and `emergency`.
+    pub fn methods_for(&self, state: &str) -> Vec<String> {
+        let (methods, default): (&Option<Vec<String>>, &[&str]) = match state {
+            "alert" => (&self.alert_method, &["visual"]),
+            "warn" => (&self.warn_method, &["visual"]),
+            "alarm" => (&self.alarm_method, &["sound", "visual"]),
+            "emergency" => (&self.emergency_method, &["sound", "visual"]),
+            _ => (&None, &[]),
+        };
+        methods
+            .clone()
+            .unwrap_or_else(|| default.iter().map(|method| method.to_string()).collect())
+    }
 }
 
 #[derive(Default)]
@@ -486,6 +749,12 @@ impl V1MetaZone {
     pub fn builder() -> V1MetaZoneBuilder {
         V1MetaZoneBuilder::default()
     }
+
+    /// Check if a value is in the zone, `lower` is inclusive and `upper` exclusive
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower.map_or(true, |lower| value >= lower)
+            && self.upper.map_or(true, |upper| value < upper)
+    }
 }
 
 #[derive(Default)]
@@ -554,7 +823,7 @@ impl V1Attr {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1DefSource {
     pub label: String,
@@ -573,6 +842,20 @@ impl V1DefSource {
     pub fn builder() -> V1DefSourceBuilder {
         V1DefSourceBuilder::default()
     }
+
+    /// The `$source` reference for this source, e.g. `n2k.115` or `nmea1.II`
+    pub fn ref_source(&self) -> String {
+        let id = self
+            .can_name
+            .as_ref()
+            .or(self.src.as_ref())
+            .or(self.talker.as_ref());
+        if let Some(id) = id {
+            format!("{}.{}", self.label, id)
+        } else {
+            self.label.clone()
+        }
+    }
 }
 
 #[derive(Default)]
@@ -658,13 +941,7 @@ impl V1DateTime {
         if value.is_null() {
             None
         } else {
-            let type_result: Result<V1DateTime, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(type_value) = type_result {
-                Some(type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 
@@ -782,12 +1059,47 @@ impl V1StringValue {
         if value.is_null() {
             None
         } else {
-            let type_result: Result<V1StringValue, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(type_value) = type_result {
-                Some(type_value)
-            } else {
-                None
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+}
+
+impl UpdatableValue for V1StringValue {
+    fn set_value(&mut self, value: &Value) {
+        let string_value = value.as_str().map(|s| s.to_string());
+        match self {
+            V1StringValue::Expanded(ref mut expanded) => expanded.value = string_value,
+            V1StringValue::Value(ref mut s) => *s = string_value.unwrap_or_default(),
+        }
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if info.is_empty() {
+            return;
+        }
+        if let V1StringValue::Value(ref s) = self {
+            *self = V1StringValue::Expanded(V1StringExpandedValue {
+                value: Some(s.clone()),
+                common_value_fields: None,
+                values: None,
+            });
+        }
+        if let V1StringValue::Expanded(ref mut expanded) = self {
+            expanded
+                .common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let V1StringValue::Expanded(ref mut expanded) = self {
+            if let Some(ref common) = expanded.common_value_fields {
+                add_source_value(
+                    &mut expanded.values,
+                    &common.source,
+                    || common.source_value(expanded.value.clone()),
+                    value,
+                    info,
+                );
             }
         }
     }
@@ -813,6 +1125,7 @@ impl V1StringValueBuilder {
             V1StringValue::Expanded(V1StringExpandedValue {
                 value: self.value,
                 common_value_fields: self.common_value_fields,
+                values: None,
             })
         } else if let Some(value) = self.value {
             V1StringValue::Value(value.clone())
@@ -827,11 +1140,12 @@ pub struct V1StringExpandedValue {
     pub value: Option<String>,
     #[serde(flatten)]
     pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<String>>>,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::definitions::{V1DateTime, V1DateTimeValue};
+    use crate::definitions::{V1DateTime, V1DateTimeValue, V1Meta, V1MetaZone};
 
     #[test]
     fn datetime_object_json() {
@@ -890,4 +1204,50 @@ mod tests {
         println!("{:?}", date_time);
         assert!(matches!(date_time, V1DateTime::String(_)));
     }
+
+    fn depth_zones() -> V1Meta {
+        V1Meta::builder()
+            .zones(
+                V1MetaZone::builder()
+                    .upper(1.5)
+                    .state("alarm".into())
+                    .build(),
+            )
+            .zones(
+                V1MetaZone::builder()
+                    .lower(1.5)
+                    .upper(3.0)
+                    .state("warn".into())
+                    .build(),
+            )
+            .zones(
+                V1MetaZone::builder()
+                    .lower(3.0)
+                    .state("normal".into())
+                    .build(),
+            )
+            .alarm_method("sound".into())
+            .build()
+    }
+
+    #[test]
+    fn zone_for_value() {
+        let meta = depth_zones();
+        assert_eq!(meta.zone_for(1.0).unwrap().state, "alarm");
+        assert_eq!(meta.zone_for(1.5).unwrap().state, "warn");
+        assert_eq!(meta.zone_for(3.0).unwrap().state, "normal");
+        assert_eq!(V1Meta::default().zone_for(3.0), None);
+    }
+
+    #[test]
+    fn methods_for_state() {
+        let meta = depth_zones();
+        assert_eq!(meta.methods_for("alarm"), vec!["sound".to_string()]);
+        assert_eq!(meta.methods_for("warn"), vec!["visual".to_string()]);
+        assert_eq!(
+            meta.methods_for("emergency"),
+            vec!["sound".to_string(), "visual".to_string()]
+        );
+        assert_eq!(meta.methods_for("normal"), Vec::<String>::new());
+    }
 }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
-use crate::{V1DefSource, V1Meta};
+use crate::{V1DefSource, V1Meta, V1PathValue};
 
 /// Root structure for Delta Signal K data
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
@@ -13,6 +16,53 @@ impl V1DeltaFormat {
     pub fn builder() -> V1DeltaFormatBuilder {
         V1DeltaFormatBuilder::default()
     }
+
+    /// Create a delta message setting a list of leaf values in one context
+    ///
+    /// The values are grouped into one update for each `$source` and timestamp,
+    /// meta data is added as `V1UpdateMeta`. Leaves directly on the context,
+    /// like `name`, are sent as an object on the empty path.
+    pub fn from_leaves(
+        context: String,
+        leaves: impl IntoIterator<Item = V1PathValue>,
+    ) -> V1DeltaFormat {
+        type Group = (Vec<V1UpdateValue>, Vec<V1UpdateMeta>);
+        let mut groups: BTreeMap<(Option<String>, Option<String>), Group> = BTreeMap::new();
+        for leaf in leaves {
+            let (values, meta) = groups.entry((leaf.source, leaf.timestamp)).or_default();
+            if let Some(leaf_meta) = leaf.meta {
+                meta.push(V1UpdateMeta::new(leaf.path.clone(), leaf_meta));
+            }
+            if leaf.path.contains('.') {
+                values.push(V1UpdateValue::new(leaf.path, leaf.value));
+            } else {
+                let mut root = serde_json::Map::new();
+                root.insert(leaf.path, leaf.value);
+                values.push(V1UpdateValue::new(
+                    "".to_string(),
+                    serde_json::Value::Object(root),
+                ));
+            }
+        }
+        let mut delta = V1DeltaFormat::builder().context(context);
+        for ((source, timestamp), (values, meta)) in groups {
+            let mut update = V1UpdateType::builder();
+            if let Some(source) = source {
+                update = update.ref_source(source);
+            }
+            if let Some(timestamp) = timestamp {
+                update = update.timestamp(timestamp);
+            }
+            for value in values {
+                update = update.add_update(value);
+            }
+            for meta in meta {
+                update = update.meta(meta);
+            }
+            delta = delta.add_update(update.build());
+        }
+        delta.build()
+    }
 }
 
 #[derive(Default)]
@@ -139,3 +189,66 @@ impl V1UpdateMeta {
         Self { path, value }
     }
 }
+
+/// Information from an update that is stored with each leaf it writes
+#[derive(PartialEq, Debug, Default, Clone)]
+pub struct V1UpdateInfo {
+    pub timestamp: Option<String>,
+    pub ref_source: Option<String>,
+    pub source: Option<V1DefSource>,
+    pub meta: Option<V1Meta>,
+    /// Only record the value under `values`, keeping the current value
+    pub source_value_only: bool,
+    /// Only attach the meta data, keeping the current value
+    pub meta_only: bool,
+}
+
+impl V1UpdateInfo {
+    /// The timestamp and sources of an update
+    ///
+    /// When the update only has a `source` object the `$source` reference is
+    /// made from it.
+    pub fn from_update(update: &V1UpdateType) -> Self {
+        Self {
+            timestamp: update.timestamp.clone(),
+            ref_source: update
+                .ref_source
+                .clone()
+                .or_else(|| update.source.as_ref().map(|source| source.ref_source())),
+            source: update.source.clone(),
+            meta: None,
+            source_value_only: false,
+            meta_only: false,
+        }
+    }
+
+    pub fn with_meta(meta: V1Meta) -> Self {
+        Self {
+            meta: Some(meta),
+            meta_only: true,
+            ..Default::default()
+        }
+    }
+
+    /// The update leaves the current value as it is
+    ///
+    /// Plain values, like names, have no `values` or meta data, they are only
+    /// written when this is false.
+    pub fn keeps_value(&self) -> bool {
+        self.source_value_only || self.meta_only
+    }
+
+    /// The time of the update, when it has a valid timestamp
+    pub fn time(&self) -> Option<OffsetDateTime> {
+        self.timestamp
+            .as_ref()
+            .and_then(|timestamp| OffsetDateTime::parse(timestamp, &Rfc3339).ok())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamp.is_none()
+            && self.ref_source.is_none()
+            && self.source.is_none()
+            && self.meta.is_none()
+    }
+}
@@ -1,5 +1,7 @@
-use crate::definitions::V1StringValue;
-use crate::{V1CommonValueFields, V1NumberValue};
+use crate::definitions::{update_value, V1StringValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, F64CompatiblePath};
+use crate::{SignalKGetError, V1CommonValueFields, V1NumberValue};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -16,18 +18,24 @@ pub struct V1Design {
 }
 
 impl V1Design {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         if path.is_empty() {
             return;
         }
         match path[0] {
-            "displacement" => self.displacement = V1NumberValue::from_value(value),
+            "aisShipType" | "draft" | "length" | "keel" | "rigging" if info.keeps_value() => {}
+            "displacement" => update_value(&mut self.displacement, value, info),
             "aisShipType" => self.ais_ship_type = V1DesignAisShipType::from_value(value),
             "draft" => self.draft = V1DesignDraft::from_value(value),
             "length" => self.length = V1DesignLength::from_value(value),
             "keel" => self.keel = V1DesignKeel::from_value(value),
-            "beam" => self.beam = V1NumberValue::from_value(value),
-            "airHeight" => self.air_height = V1NumberValue::from_value(value),
+            "beam" => update_value(&mut self.beam, value, info),
+            "airHeight" => update_value(&mut self.air_height, value, info),
             "rigging" => self.rigging = V1DesignRigging::from_value(value),
             &_ => {
                 log::warn!("V1Design: Unknown update pattern: {:?}::{:?}", path, value);
@@ -36,6 +44,50 @@ impl V1Design {
     }
 }
 
+impl F64CompatiblePath for V1Design {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "displacement" => get_f64_value(&self.displacement),
+            "aisShipType" => Err(SignalKGetError::WrongDataType),
+            "draft" => {
+                if let Some(ref draft) = self.draft {
+                    path.remove(0);
+                    draft.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "length" => {
+                if let Some(ref length) = self.length {
+                    path.remove(0);
+                    length.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "keel" => {
+                if let Some(ref keel) = self.keel {
+                    path.remove(0);
+                    keel.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "beam" => get_f64_value(&self.beam),
+            "airHeight" => get_f64_value(&self.air_height),
+            "rigging" => {
+                if let Some(ref rigging) = self.rigging {
+                    path.remove(0);
+                    rigging.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1DesignAisShipType {
@@ -49,13 +101,7 @@ impl V1DesignAisShipType {
         if value.is_null() {
             None
         } else {
-            let ship_type_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(ship_type_value) = ship_type_result {
-                Some(ship_type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -80,13 +126,22 @@ impl V1DesignDraft {
         if value.is_null() {
             None
         } else {
-            let draft_result: Result<V1DesignDraft, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(draft_value) = draft_result {
-                Some(draft_value)
-            } else {
-                None
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    fn get_f64_for_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        if let Some(ref value) = self.value {
+            match path.first() {
+                Some(&"minimum") => value.minimum.ok_or(SignalKGetError::ValueNotSet),
+                Some(&"maximum") => value.maximum.ok_or(SignalKGetError::ValueNotSet),
+                Some(&"current") => value.current.ok_or(SignalKGetError::ValueNotSet),
+                Some(&"canoe") => value.canoe.ok_or(SignalKGetError::ValueNotSet),
+                Some(_) => Err(SignalKGetError::NoSuchPath),
+                None => Err(SignalKGetError::WrongDataType),
             }
+        } else {
+            Err(SignalKGetError::ValueNotSet)
         }
     }
 }
@@ -113,13 +168,21 @@ impl V1DesignLength {
         if value.is_null() {
             None
         } else {
-            let length_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(length_value) = length_result {
-                Some(length_value)
-            } else {
-                None
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    fn get_f64_for_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        if let Some(ref value) = self.value {
+            match path.first() {
+                Some(&"overall") => value.overall.ok_or(SignalKGetError::ValueNotSet),
+                Some(&"hull") => value.hull.ok_or(SignalKGetError::ValueNotSet),
+                Some(&"waterline") => value.waterline.ok_or(SignalKGetError::ValueNotSet),
+                Some(_) => Err(SignalKGetError::NoSuchPath),
+                None => Err(SignalKGetError::WrongDataType),
             }
+        } else {
+            Err(SignalKGetError::ValueNotSet)
         }
     }
 }
@@ -147,13 +210,17 @@ impl V1DesignKeel {
         if value.is_null() {
             None
         } else {
-            let keel_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(keel_value) = keel_result {
-                Some(keel_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    fn get_f64_for_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path.first() {
+            Some(&"type") => Err(SignalKGetError::WrongDataType),
+            Some(&"angle") => get_f64_value(&self.angle),
+            Some(&"lift") => get_f64_value(&self.lift),
+            Some(_) => Err(SignalKGetError::NoSuchPath),
+            None => Err(SignalKGetError::WrongDataType),
         }
     }
 }
@@ -188,13 +255,16 @@ impl V1DesignRigging {
         if value.is_null() {
             None
         } else {
-            let keel_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(keel_value) = keel_result {
-                Some(keel_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    fn get_f64_for_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path.first() {
+            Some(&"type") => Err(SignalKGetError::WrongDataType),
+            Some(&"masts") => get_f64_value(&self.masts),
+            Some(_) => Err(SignalKGetError::NoSuchPath),
+            None => Err(SignalKGetError::WrongDataType),
         }
     }
 }
@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::definitions::{V1CommonValueFields, V1NumberValue, V2NumberValue};
+use crate::definitions::{update_value, V1CommonValueFields, V1NumberValue, V2NumberValue};
+use crate::delta::V1UpdateInfo;
 use crate::{helper_functions, SignalKGetError};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
@@ -20,7 +21,12 @@ impl V1Electrical {
         V1ElectricalBuilder::default()
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
             "batteries" => {
                 if self.batteries.is_none() {
@@ -40,7 +46,7 @@ impl V1Electrical {
                     if let Some(ref mut battery) = t {
                         path.remove(0); // Remove batteries
                         path.remove(0); // and the index of the bank
-                        battery.update(path, value);
+                        battery.update(path, value, info);
                     }
                 }
             }
@@ -444,7 +450,6 @@ impl V1ElectricalDCCurrentValueBuilder {
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1ElectricalDCCurrentMeta {
-    #[serde(flatten)]
     pub warn_upper: Option<f64>,
     pub warn_lower: Option<f64>,
     pub fault_upper: Option<f64>,
@@ -635,16 +640,21 @@ impl V1BatteryCapacity {
     pub fn builder() -> V1BatteryCapacityBuilder {
         V1BatteryCapacityBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
-            "nominal" => self.nominal = V2NumberValue::from_value(value),
-            "actual" => self.actual = V2NumberValue::from_value(value),
-            "remaining" => self.remaining = V2NumberValue::from_value(value),
-            "dischargeLimit" => self.discharge_limit = V2NumberValue::from_value(value),
-            "stateOfCharge" => self.state_of_charge = V2NumberValue::from_value(value),
-            "stateOfHealth" => self.state_of_health = V2NumberValue::from_value(value),
-            "dischargeSinceFull" => self.discharge_since_full = V2NumberValue::from_value(value),
-            "timeRemaining" => self.time_remaining = V2NumberValue::from_value(value),
+            "nominal" => update_value(&mut self.nominal, value, info),
+            "actual" => update_value(&mut self.actual, value, info),
+            "remaining" => update_value(&mut self.remaining, value, info),
+            "dischargeLimit" => update_value(&mut self.discharge_limit, value, info),
+            "stateOfCharge" => update_value(&mut self.state_of_charge, value, info),
+            "stateOfHealth" => update_value(&mut self.state_of_health, value, info),
+            "dischargeSinceFull" => update_value(&mut self.discharge_since_full, value, info),
+            "timeRemaining" => update_value(&mut self.time_remaining, value, info),
             &_ => {
                 log::warn!(
                     "V1BatteryCapacity: Unknown value to update: {:?}::{:?}",
@@ -654,6 +664,20 @@ impl V1BatteryCapacity {
             }
         }
     }
+
+    pub fn get_f64_for_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "nominal" => helper_functions::get_f64_value(&self.nominal),
+            "actual" => helper_functions::get_f64_value(&self.actual),
+            "remaining" => helper_functions::get_f64_value(&self.remaining),
+            "dischargeLimit" => helper_functions::get_f64_value(&self.discharge_limit),
+            "stateOfCharge" => helper_functions::get_f64_value(&self.state_of_charge),
+            "stateOfHealth" => helper_functions::get_f64_value(&self.state_of_health),
+            "dischargeSinceFull" => helper_functions::get_f64_value(&self.discharge_since_full),
+            "timeRemaining" => helper_functions::get_f64_value(&self.time_remaining),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
 }
 
 #[derive(Default)]
@@ -733,7 +757,12 @@ impl V1Battery {
     pub fn builder() -> V1BatteryBuilder {
         V1BatteryBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
             // V1ElectricalIdentity
             // name
@@ -775,7 +804,7 @@ impl V1Battery {
                 }
                 if let Some(ref mut capacity) = self.capacity {
                     path.remove(0);
-                    capacity.update(path, value);
+                    capacity.update(path, value, info);
                 }
             }
             &_ => {
@@ -803,7 +832,36 @@ impl V1Battery {
                     Err(SignalKGetError::ValueNotSet)
                 }
             }
-            &_ => Err(SignalKGetError::TBD),
+            "current" => {
+                if let Some(ref dc) = self.dc_qualities {
+                    if let Some(ref current) = dc.current {
+                        helper_functions::get_f64_value(&current.value)
+                    } else {
+                        Err(SignalKGetError::ValueNotSet)
+                    }
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "temperature" => {
+                if let Some(ref temperature) = self.temperature {
+                    helper_functions::get_f64_value(&temperature.value)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "capacity" => {
+                if let Some(ref capacity) = self.capacity {
+                    path.remove(0);
+                    capacity.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "lifetimeDischarge" => helper_functions::get_f64_value(&self.lifetime_discharge),
+            "lifetimeRecharge" => helper_functions::get_f64_value(&self.lifetime_recharge),
+            "chemistry" => Err(SignalKGetError::WrongDataType),
+            &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 }
@@ -2,7 +2,11 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::definitions::{V1CommonValueFields, V1NumberValue, V2NumberValue};
+use crate::definitions::{
+    add_source_value, update_value, UpdatableValue, V1CommonValueFields, V1NumberValue,
+    V1SourceValue, V2NumberValue,
+};
+use crate::delta::V1UpdateInfo;
 use crate::helper_functions::{
     get_f64_value, get_f64_value_for_path, get_path, F64CompatiblePath, Path,
 };
@@ -33,25 +37,43 @@ impl Path<f64> for V1Environment {
         match path[0] {
             "outside" => get_path(path, &self.outside.as_ref()),
             "inside" => get_path(path, &self.inside.as_ref()),
-            "water" => Err(SignalKGetError::TBD), // get_path(path, &self.water.as_ref()),
-            "depth" => Err(SignalKGetError::TBD), // get_path(path, &self.depth.as_ref()),
-            "current" => Err(SignalKGetError::TBD), // get_path(path, &self.current.as_ref()),
-            "tide" => Err(SignalKGetError::TBD),  // get_path(path, &self.tide.as_ref()),
+            "water" => get_gettable_path(path, &self.water),
+            "depth" => get_gettable_path(path, &self.depth),
+            "current" => get_f64_value_for_path(&mut path.to_vec(), &self.current),
+            "tide" => get_gettable_path(path, &self.tide),
             "heave" => get_f64_value(&self.heave),
-            "wind" => Err(SignalKGetError::TBD), // get_path(path, &self.wind.as_ref()),
-            "time" => Err(SignalKGetError::TBD), // get_path(path, &self.time.as_ref()),
-            "mode" => Err(SignalKGetError::TBD), // get_path(path, &self.mode.as_ref()),
+            "wind" => get_gettable_path(path, &self.wind),
+            "time" => get_gettable_path(path, &self.time),
+            "mode" => get_gettable_path(path, &self.mode),
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 }
 
+fn get_gettable_path(
+    path: &[&str],
+    value: &Option<impl F64Gettable>,
+) -> Result<f64, SignalKGetError> {
+    if path.len() < 2 {
+        Err(SignalKGetError::WrongDataType)
+    } else if let Some(ref item) = value {
+        item.get_f64_for_path(&mut path[1..].to_vec())
+    } else {
+        Err(SignalKGetError::ValueNotSet)
+    }
+}
+
 impl V1Environment {
     pub fn builder() -> V1EnvironmentBuilder {
         V1EnvironmentBuilder::default()
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         log::debug!("environment update: {:?} -> {:?}", path, value);
         match path[0] {
             "outside" => {
@@ -60,7 +82,7 @@ impl V1Environment {
                 }
                 if let Some(ref mut outside) = self.outside {
                     path.remove(0);
-                    outside.update(path, value);
+                    outside.update(path, value, info);
                 }
             }
             "inside" => {
@@ -69,7 +91,7 @@ impl V1Environment {
                 }
                 if let Some(ref mut inside) = self.inside {
                     path.remove(0);
-                    inside.update(path, value);
+                    inside.update(path, value, info);
                 }
             }
             "water" => {
@@ -78,7 +100,7 @@ impl V1Environment {
                 }
                 if let Some(ref mut water) = self.water {
                     path.remove(0);
-                    water.update(path, value);
+                    water.update(path, value, info);
                 }
             }
             "depth" => {
@@ -87,35 +109,27 @@ impl V1Environment {
                 }
                 if let Some(ref mut depth) = self.depth {
                     path.remove(0);
-                    depth.update(path, value);
-                }
-            }
-            "current" => {
-                if self.current.is_none() {
-                    self.current = Some(V1EnvironmentCurrent::default());
-                }
-                if let Some(ref mut current) = self.current {
-                    path.remove(0);
-                    current.update(path, value);
+                    depth.update(path, value, info);
                 }
             }
+            "current" => update_value(&mut self.current, value, info),
             "tide" => {
                 if self.tide.is_none() {
                     self.tide = Some(V1EnvironmentTide::default());
                 }
                 if let Some(ref mut tide) = self.tide {
                     path.remove(0);
-                    tide.update(path, value);
+                    tide.update(path, value, info);
                 }
             }
-            "heave" => self.heave = Some(V1NumberValue::builder().json_value(value).build()),
+            "heave" => update_value(&mut self.heave, value, info),
             "wind" => {
                 if self.wind.is_none() {
                     self.wind = Some(V1EnvironmentWind::default());
                 }
                 if let Some(ref mut wind) = self.wind {
                     path.remove(0);
-                    wind.update(path, value);
+                    wind.update(path, value, info);
                 }
             }
             "time" => {
@@ -124,7 +138,7 @@ impl V1Environment {
                 }
                 if let Some(ref mut time) = self.time {
                     path.remove(0);
-                    time.update(path, value);
+                    time.update(path, value, info);
                 }
             }
             "mode" => {
@@ -133,7 +147,7 @@ impl V1Environment {
                 }
                 if let Some(ref mut mode) = self.mode {
                     path.remove(0);
-                    mode.update(path, value);
+                    mode.update(path, value, info);
                 }
             }
 
@@ -353,7 +367,12 @@ impl V1EnvironmentOutside {
     pub fn builder() -> V1EnvironmentOutsideBuilder {
         V1EnvironmentOutsideBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentOutside update: {:?} -> {:?}", path, value);
         match path[0] {
             &_ => {
@@ -462,7 +481,7 @@ impl Path<f64> for V1EnvironmentInside {
                 Err(SignalKGetError::ValueNotSet)
             }
         } else {
-            let ref zone = self.zones.get(path[0]);
+            let zone = &self.zones.get(path[0]);
             get_path(path, zone)
         }
     }
@@ -472,7 +491,12 @@ impl V1EnvironmentInside {
     pub fn builder() -> V1EnvironmentInsideBuilder {
         V1EnvironmentInsideBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentOutside update: {:?} -> {:?}", path, value);
         log::warn!(
             "V1EnvironmentInside: Unknown value to update: {:?}::{:?}",
@@ -547,7 +571,12 @@ impl V1EnvironmentZone {
     pub fn builder() -> V1EnvironmentZoneBuilder {
         V1EnvironmentZoneBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentZone update: {:?} -> {:?}", path, value);
         log::warn!(
             "V1EnvironmentZone: Unknown value to update: {:?}::{:?}",
@@ -635,19 +664,16 @@ impl V1EnvironmentWater {
             salinity,
         }
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentWater update: {:?} -> {:?}", path, value);
         match path[0] {
-            "temperature" => {
-                if let Some(val) = value.as_f64() {
-                    self.temperature = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "salinity" => {
-                if let Some(val) = value.as_f64() {
-                    self.salinity = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
+            "temperature" => update_value(&mut self.temperature, value, info),
+            "salinity" => update_value(&mut self.salinity, value, info),
             &_ => {
                 log::warn!(
                     "V1EnvironmentWater: Unknown value to update: {:?}::{:?}",
@@ -683,34 +709,19 @@ impl V1EnvironmentDepth {
     pub fn builder() -> V1EnvironmentDepthBuilder {
         V1EnvironmentDepthBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentDepth update: {:?} -> {:?}", path, value);
         match path[0] {
-            "belowKeel" => {
-                if let Some(val) = value.as_f64() {
-                    self.below_keel = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "belowTransducer" => {
-                if let Some(val) = value.as_f64() {
-                    self.below_transducer = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "belowSurface" => {
-                if let Some(val) = value.as_f64() {
-                    self.below_surface = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "transducerToKeel" => {
-                if let Some(val) = value.as_f64() {
-                    self.transducer_to_keel = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "surfaceToTransducer" => {
-                if let Some(val) = value.as_f64() {
-                    self.surface_to_transducer = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
+            "belowKeel" => update_value(&mut self.below_keel, value, info),
+            "belowTransducer" => update_value(&mut self.below_transducer, value, info),
+            "belowSurface" => update_value(&mut self.below_surface, value, info),
+            "transducerToKeel" => update_value(&mut self.transducer_to_keel, value, info),
+            "surfaceToTransducer" => update_value(&mut self.surface_to_transducer, value, info),
             &_ => {
                 log::warn!(
                     "V1EnvironmentDepth: Unknown value to update: {:?}::{:?}",
@@ -782,7 +793,7 @@ pub struct V1EnvironmentCurrent {
     #[serde(flatten)]
     pub common: Option<V1CommonValueFields>,
     pub value: Option<V1EnvironmentCurrentValue>,
-    pub values: Option<HashMap<String, V1EnvironmentCurrentType>>,
+    pub values: Option<HashMap<String, V1SourceValue<V1EnvironmentCurrentValue>>>,
 }
 
 impl F64CompatiblePath for V1EnvironmentCurrent {
@@ -802,58 +813,40 @@ impl F64CompatiblePath for V1EnvironmentCurrent {
                     Err(SignalKGetError::ValueNotSet)
                 }
             }
+            "setMagnetic" => {
+                if let Some(ref val) = &self.value {
+                    val.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 }
 
-impl V1EnvironmentCurrent {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
-        log::debug!("V1EnvironmentCurrent update: {:?} -> {:?}", path, value);
-        let val: Result<V1EnvironmentCurrentValue, serde_json::Error> =
-            serde_json::from_value(value.clone());
-        if let Ok(cur) = val {
-            log::debug!("V1EnvironmentCurrent value: {:?}", cur);
-            self.value = Some(cur);
-        } else {
-            log::warn!(
-                "V1EnvironmentCurrent: Unknown value to update: {:?}::{:?}",
-                path,
-                value
-            );
+impl UpdatableValue for V1EnvironmentCurrent {
+    fn set_value(&mut self, value: &serde_json::value::Value) {
+        log::debug!("V1EnvironmentCurrent value: {:?}", value);
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
         }
     }
-}
-
-impl F64Gettable for V1EnvironmentCurrent {
-    fn get_f64_for_path(&self, _path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
-        Err(SignalKGetError::TBD)
-    }
-}
-
-#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct V1EnvironmentCurrentType {
-    pub timestamp: Option<String>,
-    pub pgn: Option<f64>,
-    pub sentence: Option<String>,
-    pub value: Option<V1EnvironmentCurrentValue>,
-}
-
-impl V1EnvironmentCurrentType {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
-        log::debug!("V1EnvironmentCurrentType update: {:?} -> {:?}", path, value);
-        log::warn!(
-            "V1EnvironmentCurrentType: Unknown value to update: {:?}::{:?}",
-            path,
-            value
-        );
-    }
-}
-
-impl F64Gettable for V1EnvironmentCurrentType {
-    fn get_f64_for_path(&self, _path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
-        Err(SignalKGetError::WrongDataType)
+    fn add_source_value(&mut self, value: &serde_json::value::Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
     }
 }
 
@@ -877,7 +870,12 @@ impl F64CompatiblePath for V1EnvironmentCurrentValue {
 }
 
 impl V1EnvironmentCurrentValue {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!(
             "V1EnvironmentCurrentValue update: {:?} -> {:?}",
             path,
@@ -902,7 +900,12 @@ pub struct V1EnvironmentTide {
 }
 
 impl V1EnvironmentTide {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentTide update: {:?} -> {:?}", path, value);
         log::warn!(
             "V1EnvironmentTide: Unknown value to update: {:?}::{:?}",
@@ -913,8 +916,15 @@ impl V1EnvironmentTide {
 }
 
 impl F64Gettable for V1EnvironmentTide {
-    fn get_f64_for_path(&self, _path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
-        Err(SignalKGetError::TBD)
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "heightHigh" => get_f64_value(&self.height_high),
+            "heightNow" => get_f64_value(&self.height_now),
+            "heightLow" => get_f64_value(&self.height_low),
+            "timeLow" => Err(SignalKGetError::WrongDataType),
+            "timeHigh" => Err(SignalKGetError::WrongDataType),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
     }
 }
 
@@ -933,54 +943,23 @@ pub struct V1EnvironmentWind {
 }
 
 impl V1EnvironmentWind {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentWind update: {:?} -> {:?}", path, value);
         match path[0] {
-            "angleApparent" => {
-                if let Some(val) = value.as_f64() {
-                    self.angle_apparent = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "angleTrueGround" => {
-                if let Some(val) = value.as_f64() {
-                    self.angle_true_ground = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "angleTrueWater" => {
-                if let Some(val) = value.as_f64() {
-                    self.angle_true_water = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "directionChangeAlarm" => {
-                if let Some(val) = value.as_f64() {
-                    self.direction_change_alarm = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "directionTrue" => {
-                if let Some(val) = value.as_f64() {
-                    self.direction_true = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "directionMagnetic" => {
-                if let Some(val) = value.as_f64() {
-                    self.direction_magnetic = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "speedTrue" => {
-                if let Some(val) = value.as_f64() {
-                    self.speed_true = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "speedOverGround" => {
-                if let Some(val) = value.as_f64() {
-                    self.speed_over_ground = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
-            "speedApparent" => {
-                if let Some(val) = value.as_f64() {
-                    self.speed_apparent = Some(V1NumberValue::builder().value(val).build())
-                }
-            }
+            "angleApparent" => update_value(&mut self.angle_apparent, value, info),
+            "angleTrueGround" => update_value(&mut self.angle_true_ground, value, info),
+            "angleTrueWater" => update_value(&mut self.angle_true_water, value, info),
+            "directionChangeAlarm" => update_value(&mut self.direction_change_alarm, value, info),
+            "directionTrue" => update_value(&mut self.direction_true, value, info),
+            "directionMagnetic" => update_value(&mut self.direction_magnetic, value, info),
+            "speedTrue" => update_value(&mut self.speed_true, value, info),
+            "speedOverGround" => update_value(&mut self.speed_over_ground, value, info),
+            "speedApparent" => update_value(&mut self.speed_apparent, value, info),
             &_ => {
                 log::warn!(
                     "V1EnvironmentWind: Unknown value to update: {:?}::{:?}",
@@ -1023,7 +1002,12 @@ impl V1EnvironmentTime {
     pub fn builder() -> V1EnvironmentTimeBuilder {
         V1EnvironmentTimeBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentTime update: {:?} -> {:?}", path, value);
         log::warn!(
             "V1EnvironmentTime: Unknown value to update: {:?}::{:?}",
@@ -1034,8 +1018,19 @@ impl V1EnvironmentTime {
 }
 
 impl F64Gettable for V1EnvironmentTime {
-    fn get_f64_for_path(&self, _path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
-        Err(SignalKGetError::TBD)
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "millis" => self
+                .millis
+                .map(|millis| millis as f64)
+                .ok_or(SignalKGetError::ValueNotSet),
+            "timezoneOffset" => self
+                .timezone_offset
+                .map(|offset| offset as f64)
+                .ok_or(SignalKGetError::ValueNotSet),
+            "timezoneRegion" => Err(SignalKGetError::WrongDataType),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
     }
 }
 
@@ -1089,7 +1084,12 @@ pub struct V1EnvironmentMode {
 }
 
 impl V1EnvironmentMode {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         log::debug!("V1EnvironmentMode update: {:?} -> {:?}", path, value);
         log::warn!(
             "V1EnvironmentMode: Unknown value to update: {:?}::{:?}",
@@ -1183,6 +1183,28 @@ mod tests {
             Ok(5.0)
         )
     }
+    #[test]
+    fn path_time_timezone_offset() {
+        init();
+        assert_eq!(
+            get_path_from_full_file(
+                ".self.environment.time.timezoneOffset",
+                "tests/specification/test_data/full-valid/datetime-timezone_in_environment.json"
+            ),
+            Ok(300.0)
+        )
+    }
+    #[test]
+    fn path_mode_is_not_f64() {
+        init();
+        assert_eq!(
+            get_path_from_full_file(
+                ".self.environment.mode.value",
+                "tests/specification/test_data/full-valid/environment-mode.json"
+            ),
+            Err(SignalKGetError::WrongDataType)
+        )
+    }
 
     fn get_path_from_full_file(path_string: &str, file_name: &str) -> Result<f64, SignalKGetError> {
         let path = Path::new(file_name);
@@ -1190,7 +1212,6 @@ mod tests {
         let reader = BufReader::new(file);
         let course_with_point: V1FullFormat = serde_json::from_reader(reader).unwrap();
         let path: Vec<&str> = path_string.split('.').collect();
-        let result = get_path(&path, &Some(&course_with_point));
-        result
+        get_path(&path, &Some(&course_with_point))
     }
 }
@@ -1,9 +1,17 @@
 use log::debug;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 
-use crate::helper_functions::{get_path, Path};
-use crate::{SignalKGetError, V1DeltaFormat, V1Sources, V1UpdateType, V1Vessel};
+use crate::helper_functions::{
+    collect_json_leaves, find_json_values, get_json_value, get_path, get_typed_value,
+    matches_pattern, remove_null_values, Path,
+};
+use crate::{
+    SignalKGetError, V1Aircraft, V1Aton, V1DeltaFormat, V1Meta, V1NotificationValue, V1Sar,
+    V1SourceValue, V1Sources, V1UpdateInfo, V1UpdateType, V1UpdateValue, V1Vessel,
+};
 
 /// These items can be updated by a V1UpdateType
 ///
@@ -26,6 +34,11 @@ pub trait Updatable {
     fn type_name(&self) -> String;
 }
 
+/// The first element of the contexts of the objects in the full format
+///
+/// Aids to navigation are kept under `aton` but use `atons` as context.
+const CONTEXT_GROUPS: [&str; 4] = ["vessels", "aircraft", "atons", "sar"];
+
 /// Root structure for Full Signal K data
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct V1FullFormat {
@@ -38,9 +51,15 @@ pub struct V1FullFormat {
 
     /// A wrapper object for vessel objects, each describing vessels in range, including this vessel.
     pub vessels: Option<HashMap<String, V1Vessel>>,
-    // TODO: Add aircraft
-    // TODO: Add aton
-    // TODO: Add sar
+
+    /// A wrapper object for aircraft, primarily intended for SAR aircraft in relation to marine search and rescue.
+    pub aircraft: Option<HashMap<String, V1Aircraft>>,
+
+    /// A wrapper object for Aids to Navigation (aton's)
+    pub aton: Option<HashMap<String, V1Aton>>,
+
+    /// A wrapper object for Search And Rescue (SAR) MMSI's usied in transponders. MOB, EPIRBS etc
+    pub sar: Option<HashMap<String, V1Sar>>,
     // Metadata about the data sources; physical interface, address, protocol, etc.
     // pub sources: Option<V1Sources>,
 }
@@ -51,21 +70,69 @@ impl Path<f64> for V1FullFormat {
         match path[0] {
             "version" => Err(SignalKGetError::WrongDataType),
             "self" => get_path(path, &self.get_self()),
-            "vessels" => Err(SignalKGetError::TBD),
-            "aircraft" => Err(SignalKGetError::TBD),
-            "aton" => Err(SignalKGetError::TBD),
-            "sar" => Err(SignalKGetError::TBD),
+            "vessels" if path.len() > 1 => get_path(
+                &path[1..],
+                &self
+                    .vessels
+                    .as_ref()
+                    .and_then(|vessels| vessels.get(path[1])),
+            ),
+            "aircraft" if path.len() > 1 => get_path(
+                &path[1..],
+                &self
+                    .aircraft
+                    .as_ref()
+                    .and_then(|aircraft| aircraft.get(path[1])),
+            ),
+            "aton" | "atons" if path.len() > 1 => get_path(
+                &path[1..],
+                &self.aton.as_ref().and_then(|aton| aton.get(path[1])),
+            ),
+            "sar" if path.len() > 1 => get_path(
+                &path[1..],
+                &self.sar.as_ref().and_then(|sar| sar.get(path[1])),
+            ),
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 }
 
+/// A populated leaf of the Signal K model with its full path
+#[derive(PartialEq, Debug, Clone)]
+pub struct V1PathValue {
+    pub path: String,
+    pub value: Value,
+    pub timestamp: Option<String>,
+    pub source: Option<String>,
+    pub meta: Option<V1Meta>,
+}
+
+impl V1PathValue {
+    pub fn new(
+        path: String,
+        value: Value,
+        timestamp: Option<String>,
+        source: Option<String>,
+    ) -> Self {
+        Self {
+            path,
+            value,
+            timestamp,
+            source,
+            meta: None,
+        }
+    }
+}
+
 impl Default for V1FullFormat {
     fn default() -> Self {
         V1FullFormat {
             version: "1.7.0".to_string(),
             self_: "".to_string(),
             vessels: None,
+            aircraft: None,
+            aton: None,
+            sar: None,
             // sources: None,
         }
     }
@@ -81,28 +148,101 @@ impl V1FullFormat {
     }
 
     pub fn apply_delta(&mut self, delta: &V1DeltaFormat) {
+        self.apply_delta_with(delta, |_, _, _, _| true);
+    }
+
+    /// Apply a delta, asking `accept` for each value if it should replace the
+    /// current value of its path
+    ///
+    /// `accept` gets the context, the path, the value and its update info.
+    /// Values that are not accepted are only recorded under `values`. A null
+    /// value for the empty path removes the object of the context.
+    pub fn apply_delta_with<F>(&mut self, delta: &V1DeltaFormat, mut accept: F)
+    where
+        F: FnMut(&str, &str, &Value, &V1UpdateInfo) -> bool,
+    {
         if let Some(ref context) = delta.context {
             let v: Vec<&str> = context.split('.').collect();
             if v.len() < 2 {
                 return;
             }
-            if v[0] == "vessels" {
-                if self.vessels.is_none() {
-                    self.vessels = Some(HashMap::new());
+            let id = v[1].to_string();
+            if removes_object(delta) {
+                self.remove_object(v[0], &id);
+                return;
+            }
+            let mut accept =
+                |path: &str, value: &Value, info: &V1UpdateInfo| accept(context, path, value, info);
+            match v[0] {
+                "vessels" => {
+                    let vessel = self
+                        .vessels
+                        .get_or_insert_with(HashMap::new)
+                        .entry(id)
+                        .or_insert_with_key(|id| V1Vessel::new_with_id(id));
+                    for update in &delta.updates {
+                        vessel.apply_update_with(update, &mut accept);
+                    }
                 }
-                if let Some(ref mut vessels) = self.vessels {
-                    let id = v[1].to_string();
-                    if !vessels.contains_key(&id) {
-                        vessels.insert(id.clone(), V1Vessel::new_with_id(&id));
+                "aircraft" => {
+                    let aircraft = self
+                        .aircraft
+                        .get_or_insert_with(HashMap::new)
+                        .entry(id)
+                        .or_insert_with_key(|id| V1Aircraft::new_with_id(id));
+                    for update in &delta.updates {
+                        aircraft.apply_update_with(update, &mut accept);
                     }
-                    let mut t = vessels.get_mut(&id);
-                    if let Some(ref mut vessel) = t {
-                        for update in &delta.updates {
-                            vessel.apply_update(update);
-                        }
+                }
+                "aton" | "atons" => {
+                    let aton = self
+                        .aton
+                        .get_or_insert_with(HashMap::new)
+                        .entry(id)
+                        .or_insert_with_key(|id| V1Aton::new_with_id(id));
+                    for update in &delta.updates {
+                        aton.apply_update_with(update, &mut accept);
                     }
                 }
+                "sar" => {
+                    let sar = self
+                        .sar
+                        .get_or_insert_with(HashMap::new)
+                        .entry(id)
+                        .or_insert_with_key(|id| V1Sar::new_with_id(id));
+                    for update in &delta.updates {
+                        sar.apply_update_with(update, &mut accept);
+                    }
+                }
+                _ => log::warn!("Unknown context in delta: {:?}", context),
+            }
+        }
+    }
+
+    /// Remove an object, e.g. a vessel that is gone from the model
+    fn remove_object(&mut self, group: &str, id: &str) {
+        match group {
+            "vessels" => {
+                if let Some(ref mut vessels) = self.vessels {
+                    vessels.remove(id);
+                }
+            }
+            "aircraft" => {
+                if let Some(ref mut aircraft) = self.aircraft {
+                    aircraft.remove(id);
+                }
             }
+            "aton" | "atons" => {
+                if let Some(ref mut aton) = self.aton {
+                    aton.remove(id);
+                }
+            }
+            "sar" => {
+                if let Some(ref mut sar) = self.sar {
+                    sar.remove(id);
+                }
+            }
+            _ => log::warn!("Unknown context in delta: {:?}", group),
         }
     }
 
@@ -112,18 +252,9 @@ impl V1FullFormat {
         match path_que[0] {
             "version" => Err(SignalKGetError::WrongDataType),
             "self" => {
-                let self_path: Vec<&str> = self.self_.split('.').collect();
-                if self_path[0] == "vessels" {
-                    if let Some(ref vessels) = self.vessels {
-                        if let Some(vessel) = vessels.get(self_path[1]) {
-                            path_que.remove(0);
-                            vessel.get_f64_for_path(&mut path_que)
-                        } else {
-                            Err(SignalKGetError::NoSuchPath)
-                        }
-                    } else {
-                        Err(SignalKGetError::NoSuchPath)
-                    }
+                if let Some(vessel) = self.get_self() {
+                    path_que.remove(0);
+                    vessel.get_f64_for_path(&mut path_que)
                 } else {
                     Err(SignalKGetError::NoSuchPath)
                 }
@@ -141,10 +272,361 @@ impl V1FullFormat {
                     Err(SignalKGetError::NoSuchPath)
                 }
             }
+            "aircraft" | "aton" | "atons" | "sar" => self.get_path(&path_que),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+
+    /// Return the value stored for a SignalK path as any deserializable type
+    ///
+    /// The path starts with `self` or the context of an object, e.g.
+    /// `self.navigation.position`, `vessels.urn:mrn:imo:mmsi:366982330.name` or
+    /// `atons.urn:mrn:imo:mmsi:993672085.navigation.position`.
+    pub fn get_for_path<T: DeserializeOwned + PartialEq>(
+        &self,
+        path: String,
+    ) -> Result<T, SignalKGetError> {
+        let path_que: Vec<&str> = path.split('.').collect();
+        log::debug!("get_for_path(&self, {:?})", path_que);
+        match path_que[0] {
+            "version" if path_que.len() == 1 => get_typed_value(&Value::from(self.version.clone())),
+            "self" if path_que.len() == 1 => get_typed_value(&Value::from(self.self_.clone())),
+            "self" => {
+                if let Some(vessel) = self.get_self() {
+                    vessel.get_for_path(&path_que[1..])
+                } else {
+                    Err(SignalKGetError::NoSuchPath)
+                }
+            }
+            "vessels" if path_que.len() > 1 => {
+                if let Some(vessel) = self
+                    .vessels
+                    .as_ref()
+                    .and_then(|vessels| vessels.get(path_que[1]))
+                {
+                    vessel.get_for_path(&path_que[2..])
+                } else {
+                    Err(SignalKGetError::NoSuchPath)
+                }
+            }
+            "aircraft" | "aton" | "atons" | "sar" if path_que.len() > 1 => {
+                get_typed_value(&self.get_json_for_path(&path)?)
+            }
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 
+    /// Return the JSON subtree stored for a SignalK path
+    ///
+    /// This gives the same data as the REST API, `self.electrical.batteries`
+    /// matches `/signalk/v1/api/vessels/self/electrical/batteries`. An empty path
+    /// gives the full tree. Fields that are not set are left out of the result.
+    pub fn get_json_for_path(&self, path: &str) -> Result<Value, SignalKGetError> {
+        let path_que: Vec<&str> = path.split('.').filter(|p| !p.is_empty()).collect();
+        log::debug!("get_json_for_path(&self, {:?})", path_que);
+        match path_que.as_slice() {
+            ["self", rest @ ..] | ["vessels", "self", rest @ ..] => {
+                if let Some(vessel) = self.get_self() {
+                    vessel.get_json_for_path(rest)
+                } else {
+                    Err(SignalKGetError::NoSuchPath)
+                }
+            }
+            ["vessels", id, rest @ ..] => {
+                if let Some(vessel) = self.vessels.as_ref().and_then(|vessels| vessels.get(*id)) {
+                    vessel.get_json_for_path(rest)
+                } else {
+                    Err(SignalKGetError::NoSuchPath)
+                }
+            }
+            [group @ ("aircraft" | "aton" | "atons" | "sar"), id, rest @ ..] => {
+                let json = self
+                    .context_objects(group)
+                    .into_iter()
+                    .find(|(key, _)| key == id)
+                    .map(|(_, json)| json)
+                    .ok_or(SignalKGetError::NoSuchPath)?;
+                let mut subtree = get_json_value(&json, rest)?.clone();
+                remove_null_values(&mut subtree);
+                Ok(subtree)
+            }
+            _ => {
+                let json =
+                    serde_json::to_value(self).map_err(|_| SignalKGetError::WrongDataType)?;
+                let mut subtree = get_json_value(&json, &path_que)?.clone();
+                remove_null_values(&mut subtree);
+                Ok(subtree)
+            }
+        }
+    }
+
+    /// Return the meta data stored for a SignalK path, like units, zones and timeout
+    pub fn get_meta_for_path(&self, path: &str) -> Result<V1Meta, SignalKGetError> {
+        let node = self.get_json_for_path(path)?;
+        if let Some(meta) = node.get("meta") {
+            serde_json::from_value(meta.clone()).map_err(|_| SignalKGetError::WrongDataType)
+        } else {
+            Err(SignalKGetError::ValueNotSet)
+        }
+    }
+
+    /// Return the value of a SignalK path from every source that reported it
+    ///
+    /// The values are keyed by `$source`. A path that only one source has
+    /// reported gives that single value.
+    pub fn get_values_for_path(
+        &self,
+        path: &str,
+    ) -> Result<HashMap<String, V1SourceValue>, SignalKGetError> {
+        let node = self.get_json_for_path(path)?;
+        source_values(&node)
+    }
+
+    /// Return the value of a SignalK path from the first of the preferred sources
+    ///
+    /// When none of the `preferred` sources has reported the path the current
+    /// value is returned. The result holds the `$source` the value came from.
+    pub fn get_preferred_value_for_path(
+        &self,
+        path: &str,
+        preferred: &[&str],
+    ) -> Result<(String, V1SourceValue), SignalKGetError> {
+        let node = self.get_json_for_path(path)?;
+        let mut values = source_values(&node)?;
+        for source in preferred {
+            if let Some(value) = values.remove(*source) {
+                return Ok((source.to_string(), value));
+            }
+        }
+        Ok(current_source_value(&node))
+    }
+
+    /// Iterate over every populated leaf of all vessels, aircraft, aids to
+    /// navigation and SAR beacons
+    ///
+    /// The paths include the context, e.g.
+    /// `vessels.urn:mrn:imo:mmsi:366982330.navigation.speedOverGround`.
+    pub fn leaves(&self) -> std::vec::IntoIter<V1PathValue> {
+        let mut leaves = Vec::new();
+        for (context, context_leaves) in self.context_leaves() {
+            leaves.extend(context_leaves.into_iter().map(|mut leaf| {
+                leaf.path = format!("{}.{}", context, leaf.path);
+                leaf
+            }));
+        }
+        leaves.sort_by(|a, b| a.path.cmp(&b.path));
+        leaves.into_iter()
+    }
+
+    /// Convert the model into delta messages, one for each context
+    ///
+    /// Applying the deltas to an empty model gives back the same data.
+    pub fn to_deltas(&self) -> Vec<V1DeltaFormat> {
+        self.context_leaves()
+            .into_iter()
+            .map(|(context, leaves)| V1DeltaFormat::from_leaves(context, leaves))
+            .collect()
+    }
+
+    /// Find the changes needed to go from this model to another one
+    ///
+    /// Every added or changed leaf in `other` is sent with its new value, leaves
+    /// missing in `other` are sent with a null value. Objects missing in `other`
+    /// are removed with a null value for the object itself, the empty path.
+    /// Contexts without changes give no delta.
+    pub fn diff(&self, other: &V1FullFormat) -> Vec<V1DeltaFormat> {
+        let mut old_contexts: HashMap<String, Vec<V1PathValue>> =
+            self.context_leaves().into_iter().collect();
+        let new_contexts: HashMap<String, Vec<V1PathValue>> =
+            other.context_leaves().into_iter().collect();
+        let mut contexts: Vec<&String> = old_contexts.keys().chain(new_contexts.keys()).collect();
+        contexts.sort();
+        contexts.dedup();
+        let contexts: Vec<String> = contexts.into_iter().cloned().collect();
+
+        let mut deltas = Vec::new();
+        for context in contexts {
+            let old_leaves: HashMap<String, V1PathValue> = old_contexts
+                .remove(&context)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|leaf| (leaf.path.clone(), leaf))
+                .collect();
+            let Some(new_leaves) = new_contexts.get(&context) else {
+                deltas.push(
+                    V1DeltaFormat::builder()
+                        .context(context)
+                        .add_update(
+                            V1UpdateType::builder()
+                                .add_update(V1UpdateValue::new("".to_string(), Value::Null))
+                                .build(),
+                        )
+                        .build(),
+                );
+                continue;
+            };
+            let mut changes: Vec<V1PathValue> = Vec::new();
+            let mut new_paths = HashSet::new();
+            for leaf in new_leaves {
+                new_paths.insert(leaf.path.clone());
+                if old_leaves.get(&leaf.path) != Some(leaf) {
+                    changes.push(leaf.clone());
+                }
+            }
+            for path in old_leaves.keys() {
+                if !new_paths.contains(path) {
+                    changes.push(V1PathValue::new(path.clone(), Value::Null, None, None));
+                }
+            }
+            if !changes.is_empty() {
+                changes.sort_by(|a, b| a.path.cmp(&b.path));
+                deltas.push(V1DeltaFormat::from_leaves(context, changes));
+            }
+        }
+        deltas
+    }
+
+    /// The populated leaves of every object, keyed by the context of the object
+    ///
+    /// The contexts are sorted and named as in delta messages, aids to
+    /// navigation use `atons`.
+    fn context_leaves(&self) -> Vec<(String, Vec<V1PathValue>)> {
+        let mut contexts = Vec::new();
+        for group in CONTEXT_GROUPS {
+            for (id, json) in self.context_objects(group) {
+                let mut leaves = Vec::new();
+                collect_json_leaves(&json, "", None, None, &mut leaves);
+                leaves.sort_by(|a, b| a.path.cmp(&b.path));
+                contexts.push((format!("{}.{}", group, id), leaves));
+            }
+        }
+        contexts.sort_by(|a, b| a.0.cmp(&b.0));
+        contexts
+    }
+
+    /// The objects of a context group as JSON, keyed by their id
+    fn context_objects(&self, group: &str) -> Vec<(String, Value)> {
+        fn to_json<T: Serialize>(objects: &Option<HashMap<String, T>>) -> Vec<(String, Value)> {
+            objects
+                .iter()
+                .flatten()
+                .filter_map(|(id, object)| {
+                    serde_json::to_value(object)
+                        .ok()
+                        .map(|json| (id.clone(), json))
+                })
+                .collect()
+        }
+        match group {
+            "vessels" => to_json(&self.vessels),
+            "aircraft" => to_json(&self.aircraft),
+            "aton" | "atons" => to_json(&self.aton),
+            "sar" => to_json(&self.sar),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Return every value matching a context and a path pattern
+    ///
+    /// Both the context and the path may contain `*` wildcards, each matching
+    /// one level, e.g. `vessels.*` with `propulsion.*.revolutions`. The result
+    /// is a sorted list of `(context, path, value)`.
+    pub fn query(&self, context: &str, path: &str) -> Vec<(String, String, Value)> {
+        let path_que: Vec<&str> = path.split('.').filter(|p| !p.is_empty()).collect();
+        log::debug!("query(&self, {:?}, {:?})", context, path_que);
+        let mut results = Vec::new();
+        for (context, json) in self.get_matching_contexts(context) {
+            let mut found = Vec::new();
+            find_json_values(&json, &path_que, "", &mut found);
+            results.extend(
+                found
+                    .into_iter()
+                    .map(|(path, value)| (context.clone(), path, value)),
+            );
+        }
+        results.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        results
+    }
+
+    fn get_matching_contexts(&self, context: &str) -> Vec<(String, Value)> {
+        let mut contexts = Vec::new();
+        let (group, id) = context.split_once('.').unwrap_or((context, "*"));
+        if context == "self" || (group == "vessels" && id == "self") {
+            let self_path: Vec<&str> = self.self_.split('.').collect();
+            let self_id = self_path.last().unwrap_or(&"");
+            if let Some(vessel) = self.get_self() {
+                if let Ok(json) = serde_json::to_value(vessel) {
+                    contexts.push((format!("vessels.{}", self_id), json));
+                }
+            }
+            return contexts;
+        }
+        let group = if group == "aton" { "atons" } else { group };
+        for name in CONTEXT_GROUPS {
+            if matches_pattern(group, name) {
+                contexts.extend(
+                    self.context_objects(name)
+                        .into_iter()
+                        .filter(|(key, _)| matches_pattern(id, key))
+                        .map(|(key, json)| (format!("{}.{}", name, key), json)),
+                );
+            }
+        }
+        contexts
+    }
+
+    /// Return the raised notifications of a vessel with their paths
+    ///
+    /// The context is `self` or `vessels.<id>`. With a state, e.g. `alarm`,
+    /// only notifications in that state are returned, otherwise all active
+    /// ones. The paths are relative to `notifications`.
+    pub fn get_notifications(
+        &self,
+        context: &str,
+        state: Option<&str>,
+    ) -> Result<Vec<(String, V1NotificationValue)>, SignalKGetError> {
+        let vessel = match context.split_once('.') {
+            None if context == "self" => self.get_self(),
+            Some(("vessels", id)) => self.get_vessel(id),
+            _ => None,
+        }
+        .ok_or(SignalKGetError::NoSuchPath)?;
+        let Some(ref notifications) = vessel.notifications else {
+            return Ok(Vec::new());
+        };
+        let found = match state {
+            Some(state) => notifications.with_state(state),
+            None => notifications.active(),
+        };
+        Ok(found
+            .into_iter()
+            .map(|(path, notification)| (path, notification.clone()))
+            .collect())
+    }
+
+    /// The notification raised at a path in the notifications of a context
+    ///
+    /// The path is below `notifications`, e.g. `navigation.anchor`.
+    pub fn get_notification(&self, context: &str, path: &str) -> Option<&V1NotificationValue> {
+        let notifications = match context.split_once('.')? {
+            ("vessels", id) => self.get_vessel(id)?.notifications.as_ref(),
+            ("aircraft", id) => self.aircraft.as_ref()?.get(id)?.notifications.as_ref(),
+            ("aton" | "atons", id) => self.aton.as_ref()?.get(id)?.notifications.as_ref(),
+            ("sar", id) => self.sar.as_ref()?.get(id)?.notifications.as_ref(),
+            _ => None,
+        }?;
+        notifications.get(path)
+    }
+
+    /// The vessel with an id, `self` is the self vessel
+    pub fn get_vessel(&self, id: &str) -> Option<&V1Vessel> {
+        if id == "self" {
+            self.get_self()
+        } else {
+            self.vessels.as_ref()?.get(id)
+        }
+    }
+
     pub fn get_self(&self) -> Option<&V1Vessel> {
         if let Some(vessels) = self.vessels.as_ref() {
             let v: Vec<&str> = self.self_.split('.').collect();
@@ -163,15 +645,58 @@ pub struct V1FullFormatBuilder {
     version: String,
     self_: String,
     vessels: Option<HashMap<String, V1Vessel>>,
+    aircraft: Option<HashMap<String, V1Aircraft>>,
+    aton: Option<HashMap<String, V1Aton>>,
+    sar: Option<HashMap<String, V1Sar>>,
     sources: Option<V1Sources>,
 }
 
+/// Check if a delta sets the object of its context, the empty path, to null
+fn removes_object(delta: &V1DeltaFormat) -> bool {
+    delta
+        .updates
+        .iter()
+        .flat_map(|update| update.values.iter().flatten())
+        .any(|value| value.path.is_empty() && value.value.is_null())
+}
+
+/// The current value of a leaf and the `$source` it came from
+fn current_source_value(node: &Value) -> (String, V1SourceValue) {
+    let field = |key: &str| node.get(key).and_then(Value::as_str).map(str::to_string);
+    let value = if node.is_object() {
+        node.get("value").cloned()
+    } else {
+        Some(node.clone())
+    };
+    let current = V1SourceValue {
+        value,
+        timestamp: field("timestamp"),
+        pgn: node.get("pgn").and_then(Value::as_f64),
+        sentence: field("sentence"),
+    };
+    (field("$source").unwrap_or_default(), current)
+}
+
+/// The values of a leaf from all sources, keyed by `$source`
+fn source_values(node: &Value) -> Result<HashMap<String, V1SourceValue>, SignalKGetError> {
+    if let Some(values) = node.get("values") {
+        serde_json::from_value(values.clone()).map_err(|_| SignalKGetError::WrongDataType)
+    } else if node.is_object() && node.get("value").is_none() {
+        Err(SignalKGetError::ValueNotSet)
+    } else {
+        Ok(HashMap::from([current_source_value(node)]))
+    }
+}
+
 impl Default for V1FullFormatBuilder {
     fn default() -> Self {
         V1FullFormatBuilder {
             version: "1.7.0".to_string(),
             self_: "".to_string(),
             vessels: None,
+            aircraft: None,
+            aton: None,
+            sar: None,
             sources: None,
         }
     }
@@ -195,6 +720,20 @@ impl V1FullFormatBuilder {
         }
         self
     }
+    pub fn add_aircraft(mut self, key: String, aircraft: V1Aircraft) -> V1FullFormatBuilder {
+        self.aircraft
+            .get_or_insert_with(HashMap::new)
+            .insert(key, aircraft);
+        self
+    }
+    pub fn add_aton(mut self, key: String, aton: V1Aton) -> V1FullFormatBuilder {
+        self.aton.get_or_insert_with(HashMap::new).insert(key, aton);
+        self
+    }
+    pub fn add_sar(mut self, key: String, sar: V1Sar) -> V1FullFormatBuilder {
+        self.sar.get_or_insert_with(HashMap::new).insert(key, sar);
+        self
+    }
     pub fn sources(mut self, sources: V1Sources) -> V1FullFormatBuilder {
         self.sources = Some(sources);
         self
@@ -204,6 +743,9 @@ impl V1FullFormatBuilder {
             version: self.version,
             self_: self.self_,
             vessels: self.vessels,
+            aircraft: self.aircraft,
+            aton: self.aton,
+            sar: self.sar,
             // sources: self.sources,
         }
     }
@@ -211,11 +753,19 @@ impl V1FullFormatBuilder {
 
 #[cfg(test)]
 mod context_tests {
-    use serde_json::{Number, Value};
+    use serde_json::{json, Number, Value};
+
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::Path;
 
+    use crate::design::V1DesignAisShipTypeValue;
+    use crate::environment::{V1EnvironmentDepth, V1EnvironmentWater};
     use crate::{
-        V1DeltaFormat, V1FullFormat, V1Navigation, V1NumberValue, V1UpdateType, V1UpdateValue,
-        V1Vessel,
+        SignalKGetError, V1AttitudeValue, V1DeltaFormat, V1Environment, V1FullFormat, V1Navigation,
+        V1NumberValue, V1PathValue, V1PositionType, V1PositionValue, V1Propulsion, V1UpdateMeta,
+        V1UpdateType, V1UpdateValue, V1Vessel,
     };
 
     #[test]
@@ -243,6 +793,720 @@ mod context_tests {
         assert_eq!(speed, Ok(5.6))
     }
 
+    #[test]
+    fn get_self_name_by_path() {
+        let data = read_full_file("tests/specification/test_data/full-valid/ais-aisShipType.json");
+        let mmsi: Result<String, SignalKGetError> = data.get_for_path("self.mmsi".to_string());
+        assert_eq!(mmsi, Ok("230099999".to_string()))
+    }
+
+    #[test]
+    fn get_ais_ship_type_by_path() {
+        let data = read_full_file("tests/specification/test_data/full-valid/ais-aisShipType.json");
+        let ship_type: Result<V1DesignAisShipTypeValue, SignalKGetError> =
+            data.get_for_path("self.design.aisShipType".to_string());
+        assert_eq!(
+            ship_type,
+            Ok(V1DesignAisShipTypeValue {
+                id: 36,
+                name: "Sailing".to_string()
+            })
+        );
+        let name: Result<String, SignalKGetError> =
+            data.get_for_path("self.design.aisShipType.name".to_string());
+        assert_eq!(name, Ok("Sailing".to_string()))
+    }
+
+    #[test]
+    fn get_vessel_position_by_path() {
+        let data = read_full_file("tests/specification/test_data/full-valid/vessel-basic_nav.json");
+        let position: Result<V1PositionValue, SignalKGetError> =
+            data.get_for_path("vessels.urn:mrn:imo:mmsi:311982330.navigation.position".to_string());
+        assert_eq!(position.unwrap().latitude, -41.156426);
+        let position: Result<V1PositionType, SignalKGetError> =
+            data.get_for_path("self.navigation.position".to_string());
+        assert_eq!(position.unwrap().value.unwrap().latitude, -41.156426);
+        let latitude = data.get_for_path::<f64>("self.navigation.position.latitude".to_string());
+        assert_eq!(latitude, Ok(-41.156426))
+    }
+
+    #[test]
+    fn get_object_value_by_path() {
+        let mut data = make_366982330_vessel();
+        data.self_ = "vessels.urn:mrn:imo:mmsi:366982330".to_string();
+        let delta = V1DeltaFormat::builder()
+            .context("vessels.urn:mrn:imo:mmsi:366982330".into())
+            .add_update(
+                V1UpdateType::builder()
+                    .add_update(V1UpdateValue::new(
+                        "navigation.attitude".into(),
+                        json!({"roll": 0.1, "pitch": 0.2, "yaw": 0.3}),
+                    ))
+                    .build(),
+            )
+            .build();
+        data.apply_delta(&delta);
+        let attitude = data.get_for_path("self.navigation.attitude".to_string());
+        assert_eq!(
+            attitude,
+            Ok(V1AttitudeValue {
+                roll: Some(0.1),
+                pitch: Some(0.2),
+                yaw: Some(0.3)
+            })
+        );
+    }
+
+    #[test]
+    fn get_environment_strings_by_path() {
+        let data = read_full_file(
+            "tests/specification/test_data/full-valid/datetime-timezone_in_environment.json",
+        );
+        let region: Result<String, SignalKGetError> =
+            data.get_for_path("self.environment.time.timezoneRegion".to_string());
+        assert_eq!(region, Ok("Europe/Helsinki".to_string()));
+        let offset = data.get_f64_for_path("self.environment.time.timezoneOffset".to_string());
+        assert_eq!(offset, Ok(300.0));
+
+        let data = read_full_file("tests/specification/test_data/full-valid/environment-mode.json");
+        let mode: Result<String, SignalKGetError> =
+            data.get_for_path("self.environment.mode".to_string());
+        assert_eq!(mode, Ok("night".to_string()))
+    }
+
+    #[test]
+    fn get_battery_values_by_path() {
+        let data =
+            read_full_file("tests/specification/test_data/full-valid/electrical-full_tree.json");
+        assert_eq!(
+            data.get_f64_for_path("self.electrical.batteries.house1.current".to_string()),
+            Ok(124.2)
+        );
+        assert_eq!(
+            data.get_f64_for_path(
+                "self.electrical.batteries.house1.capacity.stateOfCharge".to_string()
+            ),
+            Ok(81.0)
+        );
+        let chemistry: Result<String, SignalKGetError> =
+            data.get_for_path("self.electrical.batteries.house1.chemistry".to_string());
+        assert_eq!(chemistry, Ok("FLA".to_string()))
+    }
+
+    #[test]
+    fn get_for_path_errors() {
+        let data = read_full_file("tests/specification/test_data/full-valid/ais-aisShipType.json");
+        let missing: Result<String, SignalKGetError> =
+            data.get_for_path("self.design.noSuchThing".to_string());
+        assert_eq!(missing, Err(SignalKGetError::NoSuchPath));
+        let unset: Result<String, SignalKGetError> =
+            data.get_for_path("self.navigation.position".to_string());
+        assert_eq!(unset, Err(SignalKGetError::ValueNotSet));
+        let wrong: Result<f64, SignalKGetError> =
+            data.get_for_path("self.design.aisShipType.name".to_string());
+        assert_eq!(wrong, Err(SignalKGetError::WrongDataType));
+    }
+
+    #[test]
+    fn get_json_subtree_by_path() {
+        let data = read_full_file("tests/specification/test_data/full-valid/vessel-basic_nav.json");
+        assert_eq!(
+            data.get_json_for_path(
+                "vessels.urn:mrn:imo:mmsi:311982330.navigation.courseOverGroundTrue"
+            ),
+            Ok(json!({
+                "timestamp": "2015-03-06T16:57:53.643Z",
+                "$source": "sources.gps_0183_RMC",
+                "value": 245.69
+            }))
+        );
+        assert_eq!(
+            data.get_json_for_path("vessels.self.navigation.position.value.latitude"),
+            Ok(json!(-41.156426))
+        );
+        assert_eq!(data.get_json_for_path("version"), Ok(json!("1.0.0")));
+        let full = data.get_json_for_path("").unwrap();
+        assert_eq!(full["self"], json!("urn:mrn:imo:mmsi:311982330"));
+    }
+
+    #[test]
+    fn get_json_subtree_of_batteries() {
+        let data =
+            read_full_file("tests/specification/test_data/full-valid/electrical-full_tree.json");
+        let batteries = data.get_json_for_path("self.electrical.batteries").unwrap();
+        assert_eq!(batteries["house1"]["chemistry"], json!("FLA"));
+        assert_eq!(batteries["house1"]["current"]["value"], json!(124.2));
+        assert!(batteries["house1"].get("lifetimeDischarge").is_none());
+    }
+
+    #[test]
+    fn get_json_for_missing_path() {
+        let data = read_full_file("tests/specification/test_data/full-valid/vessel-basic_nav.json");
+        assert_eq!(
+            data.get_json_for_path("self.environment"),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            data.get_json_for_path("vessels.urn:mrn:imo:mmsi:1"),
+            Err(SignalKGetError::NoSuchPath)
+        );
+        assert_eq!(
+            data.get_json_for_path("spaceships"),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn query_engine_revolutions() {
+        let data = make_two_vessels_with_engines();
+        assert_eq!(
+            data.query("vessels.*", "propulsion.*.revolutions"),
+            vec![
+                (
+                    "vessels.urn:mrn:imo:mmsi:366982330".to_string(),
+                    "propulsion.port.revolutions".to_string(),
+                    json!(1800.0)
+                ),
+                (
+                    "vessels.urn:mrn:imo:mmsi:366982330".to_string(),
+                    "propulsion.starboard.revolutions".to_string(),
+                    json!(1750.0)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_speed_for_all_vessels() {
+        let data = make_two_vessels_with_engines();
+        let speeds = data.query("*", "navigation.speedOverGround");
+        assert_eq!(speeds.len(), 2);
+        assert_eq!(speeds[0].0, "vessels.urn:mrn:imo:mmsi:230099999");
+        assert_eq!(speeds[0].2, json!(7.2));
+        assert_eq!(speeds[1].0, "vessels.urn:mrn:imo:mmsi:366982330");
+        assert_eq!(speeds[1].2, json!(5.6));
+    }
+
+    #[test]
+    fn query_with_glob_and_self() {
+        let data = make_two_vessels_with_engines();
+        let speeds = data.query("vessels.urn:mrn:imo:mmsi:2300*", "navigation.speed*");
+        assert_eq!(speeds.len(), 1);
+        assert_eq!(speeds[0].1, "navigation.speedOverGround");
+        let engines = data.query("self", "propulsion.*.revolutions");
+        assert_eq!(engines.len(), 2);
+        assert!(data
+            .query("vessels.self", "navigation.noSuchThing")
+            .is_empty());
+    }
+
+    #[test]
+    fn leaves_of_battery_tree() {
+        let data =
+            read_full_file("tests/specification/test_data/full-valid/electrical-full_tree.json");
+        let prefix = "vessels.urn:mrn:signalk:uuid:c0d79334-4e25-4245-8892-54e8ccc8021d.";
+        let leaves: Vec<V1PathValue> = data.leaves().collect();
+        let current = leaves
+            .iter()
+            .find(|leaf| leaf.path == format!("{}electrical.batteries.house1.current", prefix))
+            .unwrap();
+        assert_eq!(
+            current,
+            &V1PathValue::new(
+                format!("{}electrical.batteries.house1.current", prefix),
+                json!(124.2),
+                Some("2014-08-15T19:00:15.402Z".to_string()),
+                Some("foo.bar".to_string())
+            )
+        );
+        let ripple = leaves
+            .iter()
+            .find(|leaf| {
+                leaf.path == format!("{}electrical.batteries.house1.voltage.ripple", prefix)
+            })
+            .unwrap();
+        assert_eq!(ripple.value, json!(0.12));
+        assert!(leaves
+            .iter()
+            .all(|leaf| !leaf.path.contains(".meta") && !leaf.value.is_null()));
+    }
+
+    #[test]
+    fn leaves_of_engines() {
+        let data = make_two_vessels_with_engines();
+        let paths: Vec<String> = data.leaves().map(|leaf| leaf.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "vessels.urn:mrn:imo:mmsi:230099999.mmsi",
+                "vessels.urn:mrn:imo:mmsi:230099999.navigation.speedOverGround",
+                "vessels.urn:mrn:imo:mmsi:366982330.mmsi",
+                "vessels.urn:mrn:imo:mmsi:366982330.navigation.speedOverGround",
+                "vessels.urn:mrn:imo:mmsi:366982330.propulsion.port.revolutions",
+                "vessels.urn:mrn:imo:mmsi:366982330.propulsion.starboard.revolutions",
+            ]
+        );
+    }
+
+    #[test]
+    fn deltas_from_full_model_reproduce_it() {
+        let mut data = make_366982330_vessel();
+        let vessel = V1Vessel::builder()
+            .mmsi("230099999".into())
+            .name("Motu".into())
+            .navigation(
+                V1Navigation::builder()
+                    .speed_over_ground(V1NumberValue::builder().value(7.2).build())
+                    .course_over_ground_true(V1NumberValue::builder().value(1.2).build())
+                    .position(
+                        V1PositionType::builder()
+                            .json_value(&json!({"latitude": 60.1, "longitude": 24.9}))
+                            .build(),
+                    )
+                    .build(),
+            )
+            .environment(
+                V1Environment::builder()
+                    .water(V1EnvironmentWater::new(
+                        Some(V1NumberValue::builder().value(288.0).build()),
+                        None,
+                    ))
+                    .depth(
+                        V1EnvironmentDepth::builder()
+                            .below_keel(V1NumberValue::builder().value(4.2).build())
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+        data.vessels
+            .as_mut()
+            .unwrap()
+            .insert("urn:mrn:imo:mmsi:230099999".to_string(), vessel);
+
+        let deltas = data.to_deltas();
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(
+            deltas[0].context,
+            Some("vessels.urn:mrn:imo:mmsi:230099999".to_string())
+        );
+
+        let mut copy = V1FullFormat::builder().build();
+        for delta in &deltas {
+            copy.apply_delta(delta);
+        }
+        assert_eq!(copy, data);
+    }
+
+    #[test]
+    fn deltas_are_grouped_by_source_and_timestamp() {
+        let data = read_full_file("tests/specification/test_data/full-valid/vessel-basic_nav.json");
+        let deltas = data.to_deltas();
+        assert_eq!(deltas.len(), 1);
+        let updates = &deltas[0].updates;
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].ref_source, None);
+        assert_eq!(
+            updates[0].values,
+            Some(vec![V1UpdateValue::new(
+                "".to_string(),
+                json!({"mmsi": "311982330"})
+            )])
+        );
+        assert_eq!(
+            updates[1].ref_source,
+            Some("sources.gps_0183_RMC".to_string())
+        );
+        assert_eq!(
+            updates[1].timestamp,
+            Some("2015-03-06T16:57:53.643Z".to_string())
+        );
+        let paths: Vec<&str> = updates[1]
+            .values
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|value| value.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["navigation.courseOverGroundTrue", "navigation.position"]
+        );
+    }
+
+    #[test]
+    fn deltas_keep_source_and_timestamp() {
+        for file_name in [
+            "tests/specification/test_data/full-valid/vessel-basic_nav.json",
+            "tests/specification/examples/full/signalk-depth-meta-attr.json",
+        ] {
+            let data = read_full_file(file_name);
+            let mut copy = V1FullFormat::builder().build();
+            for delta in &data.to_deltas() {
+                copy.apply_delta(delta);
+            }
+            assert_eq!(
+                copy.leaves().collect::<Vec<_>>(),
+                data.leaves().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn values_from_multiple_sources() {
+        let data = read_full_file(
+            "tests/specification/test_data/full-valid/sources-multiple_sources_for_same_path.json",
+        );
+        let values = data
+            .get_values_for_path("self.navigation.courseOverGroundMagnetic")
+            .unwrap();
+        let mut sources: Vec<&String> = values.keys().collect();
+        sources.sort();
+        assert_eq!(sources, vec!["nmea1.II", "nmea2.II"]);
+        let (source, value) = data
+            .get_preferred_value_for_path(
+                "self.navigation.courseOverGroundMagnetic",
+                &["nmea0.GP", "nmea1.II"],
+            )
+            .unwrap();
+        assert_eq!(source, "nmea1.II");
+        assert_eq!(
+            value.timestamp,
+            Some("2017-03-04T14:58:47.000Z".to_string())
+        );
+        assert_eq!(value.sentence, Some("VTG".to_string()));
+        let (source, value) = data
+            .get_preferred_value_for_path("self.navigation.position", &["nmea0.GP"])
+            .unwrap();
+        assert_eq!(source, "nmea1.II");
+        assert_eq!(
+            value.value,
+            Some(json!({"longitude": 173.1693, "latitude": -41.156426}))
+        );
+        let values = data
+            .get_values_for_path("self.environment.current")
+            .unwrap();
+        assert_eq!(
+            values["sourceLabel2.166"].value,
+            Some(json!({"setTrue": 52.2, "drift": 0.33}))
+        );
+    }
+
+    #[test]
+    fn values_from_single_source() {
+        let data = read_full_file("tests/specification/test_data/full-valid/vessel-basic_nav.json");
+        let values = data
+            .get_values_for_path("self.navigation.courseOverGroundTrue")
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert!(values.contains_key("sources.gps_0183_RMC"));
+        assert_eq!(
+            data.get_values_for_path("self.navigation.noSuchValue"),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn deltas_from_two_sources_are_kept_side_by_side() {
+        let mut data = V1FullFormat::builder().build();
+        for (source, timestamp, latitude) in [
+            ("nmea1.GP", "2024-05-01T10:00:00.000Z", 60.1),
+            ("n2k.3", "2024-05-01T10:00:00.500Z", 60.2),
+            ("nmea1.GP", "2024-05-01T10:00:01.000Z", 60.3),
+        ] {
+            let delta = V1DeltaFormat::builder()
+                .context("vessels.urn:mrn:imo:mmsi:230099999".into())
+                .add_update(
+                    V1UpdateType::builder()
+                        .add_update(V1UpdateValue::new(
+                            "navigation.position".into(),
+                            json!({"latitude": latitude, "longitude": 24.9}),
+                        ))
+                        .ref_source(source.into())
+                        .timestamp(timestamp.into())
+                        .build(),
+                )
+                .build();
+            data.apply_delta(&delta);
+        }
+        let values = data
+            .get_values_for_path("vessels.urn:mrn:imo:mmsi:230099999.navigation.position")
+            .unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            values["nmea1.GP"].value,
+            Some(json!({"latitude": 60.3, "longitude": 24.9}))
+        );
+        assert_eq!(
+            values["n2k.3"].value,
+            Some(json!({"latitude": 60.2, "longitude": 24.9}))
+        );
+        let (source, value) = data
+            .get_preferred_value_for_path(
+                "vessels.urn:mrn:imo:mmsi:230099999.navigation.position",
+                &["n2k.3"],
+            )
+            .unwrap();
+        assert_eq!(source, "n2k.3");
+        assert_eq!(
+            value.timestamp,
+            Some("2024-05-01T10:00:00.500Z".to_string())
+        );
+    }
+
+    #[test]
+    fn deltas_carry_meta() {
+        let data: V1FullFormat = serde_json::from_value(json!({
+            "vessels": {
+                "urn:mrn:imo:mmsi:230099999": {
+                    "design": {
+                        "aisShipType": {
+                            "value": { "id": 36, "name": "Sailing" },
+                            "timestamp": "2017-01-25T00:23:05Z",
+                            "$source": "a.suitable.path",
+                            "meta": { "description": "The AIS ship type" }
+                        }
+                    }
+                }
+            },
+            "version": "1.0.0",
+            "self": "vessels.urn:mrn:imo:mmsi:230099999"
+        }))
+        .unwrap();
+        let deltas = data.to_deltas();
+        let meta: Vec<&V1UpdateMeta> = deltas[0]
+            .updates
+            .iter()
+            .flat_map(|update| update.meta.iter().flatten())
+            .collect();
+        assert_eq!(meta.len(), 1);
+        assert_eq!(meta[0].path, "design.aisShipType");
+        assert_eq!(meta[0].value.description, "The AIS ship type");
+    }
+
+    #[test]
+    fn diff_of_equal_models_is_empty() {
+        let data = make_two_vessels_with_engines();
+        assert!(data.diff(&data.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_finds_changed_added_and_removed_values() {
+        let old = make_two_vessels_with_engines();
+        let mut new = old.clone();
+        let vessels = new.vessels.as_mut().unwrap();
+        let own_vessel = vessels.get_mut("urn:mrn:imo:mmsi:366982330").unwrap();
+        own_vessel.name = Some("Motu".to_string());
+        own_vessel.navigation = Some(
+            V1Navigation::builder()
+                .speed_over_ground(V1NumberValue::builder().value(6.1).build())
+                .build(),
+        );
+        own_vessel.propulsion.as_mut().unwrap().remove("starboard");
+
+        let deltas = old.diff(&new);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(
+            deltas[0].context,
+            Some("vessels.urn:mrn:imo:mmsi:366982330".to_string())
+        );
+        assert_eq!(
+            deltas[0].updates[0].values,
+            Some(vec![
+                V1UpdateValue::new("".to_string(), json!({"name": "Motu"})),
+                V1UpdateValue::new("navigation.speedOverGround".to_string(), json!(6.1)),
+                V1UpdateValue::new("propulsion.starboard.revolutions".to_string(), Value::Null),
+            ])
+        );
+    }
+
+    #[test]
+    fn diff_removes_top_level_leaves() {
+        let mut old = make_two_vessels_with_engines();
+        let own_vessel = old
+            .vessels
+            .as_mut()
+            .unwrap()
+            .get_mut("urn:mrn:imo:mmsi:366982330")
+            .unwrap();
+        own_vessel.name = Some("Motu".to_string());
+        own_vessel.flag = Some("NZ".to_string());
+        let mut new = old.clone();
+        let own_vessel = new
+            .vessels
+            .as_mut()
+            .unwrap()
+            .get_mut("urn:mrn:imo:mmsi:366982330")
+            .unwrap();
+        own_vessel.name = None;
+        own_vessel.flag = Some("SE".to_string());
+
+        let mut patched = old.clone();
+        for delta in old.diff(&new) {
+            patched.apply_delta(&delta);
+        }
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn diff_of_removed_vessel() {
+        let old = make_two_vessels_with_engines();
+        let new = make_366982330_vessel();
+        let deltas = old.diff(&new);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(
+            deltas[0].context,
+            Some("vessels.urn:mrn:imo:mmsi:230099999".to_string())
+        );
+        assert_eq!(
+            deltas[0].updates[0].values,
+            Some(vec![V1UpdateValue::new("".to_string(), Value::Null)])
+        );
+
+        let mut patched = old.clone();
+        for delta in &deltas {
+            patched.apply_delta(delta);
+        }
+        let ids = |data: &V1FullFormat| {
+            let mut ids: Vec<String> = data.vessels.as_ref().unwrap().keys().cloned().collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(&patched), ids(&new));
+    }
+
+    #[test]
+    fn update_aton_from_delta() {
+        let mut data = V1FullFormat::default();
+        let delta: V1DeltaFormat = serde_json::from_value(json!({
+            "context": "atons.urn:mrn:imo:mmsi:993672085",
+            "updates": [{
+                "values": [
+                    {"path": "", "value": {"name": "Breakwater light"}},
+                    {"path": "atonType", "value": {"id": 6, "name": "Light, With Sectors"}},
+                    {"path": "navigation.position",
+                     "value": {"latitude": -41.156426, "longitude": 173.1693}}
+                ]
+            }]
+        }))
+        .unwrap();
+        data.apply_delta(&delta);
+        let aton = &data.aton.as_ref().unwrap()["urn:mrn:imo:mmsi:993672085"];
+        assert_eq!(aton.mmsi, Some("993672085".to_string()));
+        assert_eq!(aton.name, Some("Breakwater light".to_string()));
+        assert_eq!(
+            data.get_for_path::<String>(
+                "atons.urn:mrn:imo:mmsi:993672085.atonType.name".to_string()
+            ),
+            Ok("Light, With Sectors".to_string())
+        );
+        assert_eq!(
+            data.get_f64_for_path(
+                "atons.urn:mrn:imo:mmsi:993672085.navigation.position.latitude".to_string()
+            ),
+            Ok(-41.156426)
+        );
+        assert_eq!(
+            data.get_f64_for_path("aton.urn:mrn:imo:mmsi:993672085.name".to_string()),
+            Err(SignalKGetError::WrongDataType)
+        );
+        assert_eq!(
+            data.get_f64_for_path(
+                "atons.urn:mrn:imo:mmsi:993000000.navigation.position.latitude".to_string()
+            ),
+            Err(SignalKGetError::ValueNotSet)
+        );
+    }
+
+    #[test]
+    fn aircraft_and_sar_paths() {
+        let mut data =
+            read_full_file("tests/specification/test_data/full-valid/aircraft-basic_nav.json");
+        let sar = read_full_file("tests/specification/test_data/full-valid/sar-notifications.json");
+        for delta in sar.to_deltas() {
+            data.apply_delta(&delta);
+        }
+        assert_eq!(
+            data.get_f64_for_path(
+                "aircraft.urn:mrn:imo:mmsi:111982330.navigation.courseOverGroundTrue".to_string()
+            ),
+            Ok(245.69)
+        );
+        assert_eq!(
+            data.get_for_path::<String>(
+                "sar.urn:mrn:imo:mmsi:971982330.notifications.mob.message".to_string()
+            ),
+            Ok("Man overboard".to_string())
+        );
+        let positions: Vec<String> = data
+            .query("*", "navigation.position")
+            .into_iter()
+            .map(|(context, _, _)| context)
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                "aircraft.urn:mrn:imo:mmsi:111982330".to_string(),
+                "sar.urn:mrn:imo:mmsi:971982330".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn aton_round_trip_through_deltas() {
+        let data =
+            read_full_file("tests/specification/test_data/full-valid/aton-basic_position.json");
+        let deltas = data.to_deltas();
+        assert_eq!(
+            deltas[0].context,
+            Some("atons.urn:mrn:imo:mmsi:991982330".to_string())
+        );
+        let mut copy = V1FullFormat::default();
+        for delta in deltas {
+            copy.apply_delta(&delta);
+        }
+        assert_eq!(copy.aton, data.aton);
+        assert!(data.diff(&copy).is_empty());
+    }
+
+    fn make_two_vessels_with_engines() -> V1FullFormat {
+        let mut data = make_366982330_vessel();
+        data.self_ = "vessels.urn:mrn:imo:mmsi:366982330".to_string();
+        let vessels = data.vessels.as_mut().unwrap();
+        let own_vessel = vessels.get_mut("urn:mrn:imo:mmsi:366982330").unwrap();
+        let mut propulsion = HashMap::new();
+        propulsion.insert(
+            "port".to_string(),
+            V1Propulsion::builder()
+                .revolutions(V1NumberValue::builder().value(1800.0).build())
+                .build(),
+        );
+        propulsion.insert(
+            "starboard".to_string(),
+            V1Propulsion::builder()
+                .revolutions(V1NumberValue::builder().value(1750.0).build())
+                .build(),
+        );
+        own_vessel.propulsion = Some(propulsion);
+        vessels.insert(
+            "urn:mrn:imo:mmsi:230099999".to_string(),
+            V1Vessel::builder()
+                .mmsi("230099999".into())
+                .navigation(
+                    V1Navigation::builder()
+                        .speed_over_ground(V1NumberValue::builder().value(7.2).build())
+                        .build(),
+                )
+                .build(),
+        );
+        data
+    }
+
+    fn read_full_file(file_name: &str) -> V1FullFormat {
+        let file = File::open(Path::new(file_name)).unwrap();
+        serde_json::from_reader(BufReader::new(file)).unwrap()
+    }
+
     fn assert_speed_is_5_1(data: &mut V1FullFormat) {
         assert_eq!(
             data.vessels
@@ -264,7 +1528,7 @@ mod context_tests {
     }
 
     fn make_speed_delta_for_366982330() -> V1DeltaFormat {
-        let delta = V1DeltaFormat::builder()
+        V1DeltaFormat::builder()
             .context("vessels.urn:mrn:imo:mmsi:366982330".into())
             .add_update(
                 V1UpdateType::builder()
@@ -274,12 +1538,11 @@ mod context_tests {
                     ))
                     .build(),
             )
-            .build();
-        delta
+            .build()
     }
 
     fn make_366982330_vessel() -> V1FullFormat {
-        let data = V1FullFormat::builder()
+        V1FullFormat::builder()
             .add_vessel(
                 "urn:mrn:imo:mmsi:366982330".into(),
                 V1Vessel::builder()
@@ -291,7 +1554,6 @@ mod context_tests {
                     )
                     .build(),
             )
-            .build();
-        data
+            .build()
     }
 }
@@ -1,6 +1,9 @@
 use crate::definitions::F64Compatible;
-use crate::SignalKGetError;
+use crate::full::V1PathValue;
+use crate::{SignalKGetError, V1UpdateInfo, V1UpdateType};
 use log::{debug, warn};
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 pub fn json_as_optional_string(value: &Value) -> Option<String> {
@@ -58,10 +61,289 @@ pub fn get_f64_value_for_path(
     }
 }
 
+/// Walk a serialized Signal K tree following the elements of a path.
+///
+/// Leaf objects keep their data under `value`, so when an element is not
+/// found directly the walk continues inside the `value` object, e.g.
+/// `navigation.position.latitude`.
+pub fn get_json_value<'a>(value: &'a Value, path: &[&str]) -> Result<&'a Value, SignalKGetError> {
+    debug!("get_json_value({:?}, ...)", path);
+    let mut node = value;
+    for element in path {
+        if node.is_null() {
+            return Err(SignalKGetError::ValueNotSet);
+        }
+        node = if let Some(child) = get_json_child(node, element) {
+            child
+        } else if let Some(child) = node
+            .get("value")
+            .and_then(|inner| get_json_child(inner, element))
+        {
+            child
+        } else {
+            return Err(SignalKGetError::NoSuchPath);
+        };
+    }
+    if node.is_null() {
+        Err(SignalKGetError::ValueNotSet)
+    } else {
+        Ok(node)
+    }
+}
+
+fn get_json_child<'a>(value: &'a Value, element: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(element),
+        Value::Array(list) => element.parse::<usize>().ok().and_then(|i| list.get(i)),
+        _ => None,
+    }
+}
+
+/// Check if a key matches a pattern where `*` matches any number of characters.
+pub fn matches_pattern(pattern: &str, key: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == key,
+        Some((head, tail)) => match key.strip_prefix(head) {
+            None => false,
+            Some(rest) => rest
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(std::iter::once(rest.len()))
+                .any(|i| matches_pattern(tail, &rest[i..])),
+        },
+    }
+}
+
+/// Match a dotted key against a pattern, one element at a time
+pub fn matches_path_pattern(pattern: &str, key: &str) -> bool {
+    let patterns: Vec<&str> = pattern.split('.').collect();
+    let keys: Vec<&str> = key.split('.').collect();
+    patterns.len() == keys.len()
+        && patterns
+            .iter()
+            .zip(keys.iter())
+            .all(|(pattern, key)| matches_pattern(pattern, key))
+}
+
+/// Find all nodes in a serialized Signal K tree matching a path pattern.
+///
+/// Each element of the path may contain `*` wildcards and matches exactly one
+/// level of the tree. For leaves the content of `value` is returned, the found
+/// paths are prefixed with `prefix`.
+pub fn find_json_values(
+    value: &Value,
+    path: &[&str],
+    prefix: &str,
+    results: &mut Vec<(String, Value)>,
+) {
+    if value.is_null() {
+        return;
+    }
+    let Some((element, rest)) = path.split_first() else {
+        let leaf = match value.get("value") {
+            Some(inner) if !inner.is_null() => inner.clone(),
+            _ => value.clone(),
+        };
+        results.push((prefix.to_string(), leaf));
+        return;
+    };
+    if let Value::Object(map) = value {
+        if !element.contains('*') && !map.contains_key(*element) {
+            if let Some(inner) = map.get("value") {
+                find_json_values(inner, path, prefix, results);
+            }
+            return;
+        }
+        for (key, child) in map {
+            if matches_pattern(element, key) {
+                let child_prefix = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                find_json_values(child, rest, &child_prefix, results);
+            }
+        }
+    }
+}
+
+/// Keys in a serialized Signal K tree that describe a value rather than hold data
+const VALUE_DESCRIPTION_KEYS: [&str; 8] = [
+    "timestamp",
+    "$source",
+    "source",
+    "meta",
+    "values",
+    "pgn",
+    "sentence",
+    "_attr",
+];
+
+/// Collect every populated leaf in a serialized Signal K tree.
+///
+/// A leaf is either the `value` member of an object or a plain json value. The
+/// timestamp and `$source` of an object are passed on to its members unless they
+/// have their own.
+pub fn collect_json_leaves(
+    value: &Value,
+    prefix: &str,
+    timestamp: Option<&str>,
+    source: Option<&str>,
+    leaves: &mut Vec<V1PathValue>,
+) {
+    let (timestamp, source) = match value {
+        Value::Object(map) => (
+            non_empty_str(map.get("timestamp")).or(timestamp),
+            non_empty_str(map.get("$source")).or(source),
+        ),
+        _ => (timestamp, source),
+    };
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            if let Some(inner) = map.get("value").filter(|inner| !inner.is_null()) {
+                let mut leaf = V1PathValue::new(
+                    prefix.to_string(),
+                    inner.clone(),
+                    timestamp.map(String::from),
+                    source.map(String::from),
+                );
+                leaf.meta = map
+                    .get("meta")
+                    .and_then(|meta| serde_json::from_value(meta.clone()).ok());
+                leaves.push(leaf);
+            }
+            // Members next to a value, like `voltage.ripple`, are leaves of their own
+            for (key, child) in map {
+                if key == "value" || VALUE_DESCRIPTION_KEYS.contains(&key.as_str()) {
+                    continue;
+                }
+                let child_prefix = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                collect_json_leaves(child, &child_prefix, timestamp, source, leaves);
+            }
+        }
+        _ => leaves.push(V1PathValue::new(
+            prefix.to_string(),
+            value.clone(),
+            timestamp.map(String::from),
+            source.map(String::from),
+        )),
+    }
+}
+
+/// Collect every populated leaf of an object in the full format, sorted by path
+pub fn json_leaves(object: &impl Serialize) -> std::vec::IntoIter<V1PathValue> {
+    let mut leaves = Vec::new();
+    if let Ok(json) = serde_json::to_value(object) {
+        collect_json_leaves(&json, "", None, None, &mut leaves);
+    }
+    leaves.sort_by(|a, b| a.path.cmp(&b.path));
+    leaves.into_iter()
+}
+
+/// Apply the values and meta data of an update to an object in the full format
+///
+/// `update` is the update method of the object. `accept` is asked for each
+/// value if it should replace the current value of its path, values that are
+/// not accepted are only recorded under `values`. Meta data is attached to
+/// the leaf of its path, keeping the current value.
+pub fn apply_update_values<T, U, F>(object: &mut T, update: &V1UpdateType, accept: &mut F, apply: U)
+where
+    U: Fn(&mut T, &mut Vec<&str>, &Value, &V1UpdateInfo),
+    F: FnMut(&str, &Value, &V1UpdateInfo) -> bool,
+{
+    debug!("Apply update: {:?}", update);
+    if let Some(ref values) = update.values {
+        let info = V1UpdateInfo::from_update(update);
+        let source_info = V1UpdateInfo {
+            source_value_only: true,
+            ..info.clone()
+        };
+        for value in values.iter() {
+            let mut path: Vec<&str> = value.path.split('.').collect();
+            if accept(&value.path, &value.value, &info) {
+                apply(object, &mut path, &value.value, &info);
+            } else {
+                apply(object, &mut path, &value.value, &source_info);
+            }
+        }
+    }
+    if let Some(ref metas) = update.meta {
+        for meta in metas.iter() {
+            let mut path: Vec<&str> = meta.path.split('.').collect();
+            let info = V1UpdateInfo::with_meta(meta.value.clone());
+            apply(object, &mut path, &Value::Null, &info);
+        }
+    }
+}
+
+fn non_empty_str(value: Option<&Value>) -> Option<&str> {
+    value.and_then(Value::as_str).filter(|s| !s.is_empty())
+}
+
+/// Remove all null members from a serialized Signal K tree.
+///
+/// Unset optional fields serialize as `null`, the REST API leaves them out.
+pub fn remove_null_values(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, member| !member.is_null());
+            map.values_mut().for_each(remove_null_values);
+        }
+        Value::Array(list) => list.iter_mut().for_each(remove_null_values),
+        _ => {}
+    }
+}
+
+/// Deserialize an optional object where the last of any repeated keys wins.
+///
+/// Some specification samples repeat keys in an object, which the derived
+/// deserializers reject as a duplicate field. The object is read through a JSON
+/// map first so that the last one is kept, as in other JSON parsers.
+pub fn last_key_wins<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    Option::<serde_json::Map<String, Value>>::deserialize(deserializer)?
+        .map(|map| serde_json::from_value(Value::Object(map)).map_err(D::Error::custom))
+        .transpose()
+}
+
+/// Convert a node found by `get_json_value` into the requested type.
+///
+/// The content of a `value` field is tried first, so `f64`, `String`, `bool`,
+/// enums or objects like `V1AttitudeValue` can be read from a leaf with
+/// timestamp and source. Types that read nothing from that content, like
+/// `V1PositionType`, have the shape of the leaf itself and are read from the
+/// whole node.
+pub fn get_typed_value<T: DeserializeOwned + PartialEq>(
+    value: &Value,
+) -> Result<T, SignalKGetError> {
+    match value.get("value") {
+        None => T::deserialize(value).map_err(|_| SignalKGetError::WrongDataType),
+        Some(Value::Null) => T::deserialize(value).map_err(|_| SignalKGetError::ValueNotSet),
+        Some(inner) => match T::deserialize(inner) {
+            Ok(typed) if !reads_nothing(&typed) => Ok(typed),
+            from_inner => T::deserialize(value)
+                .or(from_inner)
+                .map_err(|_| SignalKGetError::WrongDataType),
+        },
+    }
+}
+
+fn reads_nothing<T: DeserializeOwned + PartialEq>(typed: &T) -> bool {
+    T::deserialize(Value::Object(serde_json::Map::new())).is_ok_and(|empty| empty == *typed)
+}
+
 #[cfg(test)]
 mod context_tests {
     use crate::full::V1FullFormat;
-    use crate::helper_functions::get_path;
+    use crate::helper_functions::{get_path, matches_pattern};
     use std::fs::File;
     use std::io::BufReader;
     use std::path::Path;
@@ -81,4 +363,20 @@ mod context_tests {
         println!("{:?}", path);
         let _m: f64 = get_path(&path, &Some(&sk_data)).expect("Failed to get path");
     }
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(matches_pattern("*", "urn:mrn:imo:mmsi:366982330"));
+        assert!(matches_pattern(
+            "urn:mrn:imo:*",
+            "urn:mrn:imo:mmsi:366982330"
+        ));
+        assert!(matches_pattern("speed*Ground", "speedOverGround"));
+        assert!(matches_pattern("revolutions", "revolutions"));
+        assert!(!matches_pattern("revolutions", "temperature"));
+        assert!(!matches_pattern(
+            "urn:mrn:signalk:*",
+            "urn:mrn:imo:mmsi:366982330"
+        ));
+    }
 }
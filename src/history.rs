@@ -0,0 +1,281 @@
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use time::{Duration, OffsetDateTime};
+
+use crate::helper_functions::matches_path_pattern;
+use crate::{SignalKGetError, V1UpdateInfo};
+
+/// A numeric value of a path at a point in time
+#[derive(PartialEq, Debug, Clone)]
+pub struct V1HistorySample {
+    pub timestamp: OffsetDateTime,
+    pub value: f64,
+}
+
+impl V1HistorySample {
+    pub fn new(timestamp: OffsetDateTime, value: f64) -> Self {
+        Self { timestamp, value }
+    }
+}
+
+/// How much history to keep for a path
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum V1HistoryLimit {
+    /// Keep the last number of samples
+    Samples(usize),
+    /// Keep the samples within a duration of the newest sample
+    Duration(Duration),
+}
+
+/// Minimum, maximum and mean of a series of samples
+#[derive(PartialEq, Debug, Clone)]
+pub struct V1HistoryStatistics {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl V1HistoryStatistics {
+    /// Summarize samples, `None` when there are none
+    pub fn from_samples(samples: &[V1HistorySample]) -> Option<Self> {
+        let first = samples.first()?.value;
+        let (min, max, sum) = samples.iter().fold((first, first, 0.0), |acc, sample| {
+            (
+                acc.0.min(sample.value),
+                acc.1.max(sample.value),
+                acc.2 + sample.value,
+            )
+        });
+        Some(Self {
+            count: samples.len(),
+            min,
+            max,
+            mean: sum / samples.len() as f64,
+        })
+    }
+}
+
+/// Recorded numeric values for the paths history is enabled for
+///
+/// The path patterns may contain `*` wildcards, each matching one level. A
+/// series is kept for each context and path. Only values that become the
+/// current value of a path are recorded, at the timestamp of their update.
+#[derive(Debug, Default)]
+pub struct History {
+    limits: Vec<(String, V1HistoryLimit)>,
+    series: HashMap<(String, String), VecDeque<V1HistorySample>>,
+}
+
+impl History {
+    /// Start recording paths matching the pattern
+    pub fn enable(&mut self, path: &str, limit: V1HistoryLimit) {
+        self.limits.retain(|(pattern, _)| pattern != path);
+        self.limits.push((path.to_string(), limit));
+    }
+
+    /// Stop recording paths matching the pattern and forget their samples
+    pub fn disable(&mut self, path: &str) {
+        self.limits.retain(|(pattern, _)| pattern != path);
+        let limits = &self.limits;
+        self.series.retain(|(_, p), _| {
+            limits
+                .iter()
+                .any(|(pattern, _)| matches_path_pattern(pattern, p))
+        });
+    }
+
+    fn limit(&self, path: &str) -> Option<V1HistoryLimit> {
+        self.limits
+            .iter()
+            .find(|(pattern, _)| matches_path_pattern(pattern, path))
+            .map(|(_, limit)| *limit)
+    }
+
+    /// Add a sample when the path is recorded and the value is a number
+    ///
+    /// Updates without a timestamp are not recorded.
+    pub fn record(&mut self, context: &str, path: &str, value: &Value, info: &V1UpdateInfo) {
+        let Some(number) = value.as_f64() else {
+            return;
+        };
+        let Some(time) = info.time() else {
+            return;
+        };
+        let Some(limit) = self.limit(path) else {
+            return;
+        };
+        let samples = self
+            .series
+            .entry((context.to_string(), path.to_string()))
+            .or_default();
+        samples.push_back(V1HistorySample::new(time, number));
+        match limit {
+            V1HistoryLimit::Samples(count) => {
+                while samples.len() > count {
+                    samples.pop_front();
+                }
+            }
+            V1HistoryLimit::Duration(duration) => {
+                let newest = samples
+                    .iter()
+                    .map(|sample| sample.timestamp)
+                    .max()
+                    .unwrap_or(time);
+                samples.retain(|sample| newest - sample.timestamp <= duration);
+            }
+        }
+    }
+
+    /// The samples of a path, oldest first, optionally only those since a time
+    pub fn samples(
+        &self,
+        context: &str,
+        path: &str,
+        since: Option<OffsetDateTime>,
+    ) -> Result<Vec<V1HistorySample>, SignalKGetError> {
+        if self.limit(path).is_none() {
+            return Err(SignalKGetError::NoSuchPath);
+        }
+        let samples = self
+            .series
+            .get(&(context.to_string(), path.to_string()))
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| since.map_or(true, |since| sample.timestamp >= since))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod history_tests {
+    use serde_json::json;
+    use time::format_description::well_known::Rfc3339;
+    use time::{Duration, OffsetDateTime};
+
+    use crate::history::{History, V1HistoryLimit, V1HistorySample, V1HistoryStatistics};
+    use crate::{SignalKGetError, V1UpdateInfo};
+
+    fn at(seconds: i64) -> V1UpdateInfo {
+        let time = OffsetDateTime::parse("2024-05-01T10:00:00Z", &Rfc3339).unwrap()
+            + Duration::seconds(seconds);
+        V1UpdateInfo {
+            timestamp: Some(time.format(&Rfc3339).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn record_depths(history: &mut History) {
+        for (seconds, depth) in [(0, 4.0), (10, 5.0), (20, 6.0), (30, 9.0)] {
+            history.record(
+                "vessels.a",
+                "environment.depth.belowKeel",
+                &json!(depth),
+                &at(seconds),
+            );
+        }
+    }
+
+    fn values(samples: Vec<V1HistorySample>) -> Vec<f64> {
+        samples.into_iter().map(|sample| sample.value).collect()
+    }
+
+    #[test]
+    fn skips_updates_without_timestamp() {
+        let mut history = History::default();
+        history.enable("environment.depth.*", V1HistoryLimit::Samples(3));
+        record_depths(&mut history);
+        history.record(
+            "vessels.a",
+            "environment.depth.belowKeel",
+            &json!(7.0),
+            &V1UpdateInfo::default(),
+        );
+        let samples = history
+            .samples("vessels.a", "environment.depth.belowKeel", None)
+            .unwrap();
+        assert_eq!(values(samples), vec![5.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn keeps_last_samples() {
+        let mut history = History::default();
+        history.enable("environment.depth.*", V1HistoryLimit::Samples(3));
+        record_depths(&mut history);
+        let samples = history
+            .samples("vessels.a", "environment.depth.belowKeel", None)
+            .unwrap();
+        assert_eq!(values(samples), vec![5.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn keeps_samples_within_duration() {
+        let mut history = History::default();
+        history.enable(
+            "environment.depth.belowKeel",
+            V1HistoryLimit::Duration(Duration::seconds(15)),
+        );
+        record_depths(&mut history);
+        let samples = history
+            .samples("vessels.a", "environment.depth.belowKeel", None)
+            .unwrap();
+        assert_eq!(values(samples), vec![6.0, 9.0]);
+    }
+
+    #[test]
+    fn samples_since() {
+        let mut history = History::default();
+        history.enable("environment.depth.belowKeel", V1HistoryLimit::Samples(10));
+        record_depths(&mut history);
+        let since = at(10).time().unwrap();
+        let samples = history
+            .samples("vessels.a", "environment.depth.belowKeel", Some(since))
+            .unwrap();
+        assert_eq!(samples[0], V1HistorySample::new(since, 5.0));
+        assert_eq!(
+            V1HistoryStatistics::from_samples(&samples),
+            Some(V1HistoryStatistics {
+                count: 3,
+                min: 5.0,
+                max: 9.0,
+                mean: 20.0 / 3.0,
+            })
+        );
+    }
+
+    #[test]
+    fn only_enabled_numeric_paths_are_recorded() {
+        let mut history = History::default();
+        history.enable("environment.depth.belowKeel", V1HistoryLimit::Samples(10));
+        history.record("vessels.a", "name", &json!("Motu"), &at(0));
+        history.record(
+            "vessels.a",
+            "environment.depth.belowKeel",
+            &json!(null),
+            &at(0),
+        );
+        assert_eq!(
+            history.samples("vessels.a", "environment.depth.belowKeel", None),
+            Ok(vec![])
+        );
+        assert_eq!(
+            history.samples("vessels.a", "name", None),
+            Err(SignalKGetError::NoSuchPath)
+        );
+        history.disable("environment.depth.belowKeel");
+        assert_eq!(
+            history.samples("vessels.a", "environment.depth.belowKeel", None),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn statistics_of_no_samples() {
+        assert_eq!(V1HistoryStatistics::from_samples(&[]), None);
+    }
+}
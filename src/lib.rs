@@ -3,29 +3,57 @@
 //! `signalk` is a collections of types to serialize and deserialize the
 //! signal-k protocol.
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
 
+pub use aircraft::V1Aircraft;
+pub use aton::{V1Aton, V1AtonType, V1AtonTypeValue};
+pub use auth::{V1Auth, V1AuthCredentials, V1AuthLogin, V1AuthState, V1AuthToken};
 pub use definitions::{
-    V1Attr, V1CommonValueFields, V1DefSource, V1Meta, V1MetaZone, V1NumberValue,
+    V1Attr, V1CommonValueFields, V1DefSource, V1Meta, V1MetaZone, V1NumberValue, V1SourceValue,
+};
+pub use delta::{
+    V1DeltaFormat, V1UpdateInfo, V1UpdateMeta, V1UpdateType, V1UpdateValue, V1UpdateValueType,
 };
-pub use delta::{V1DeltaFormat, V1UpdateMeta, V1UpdateType, V1UpdateValue, V1UpdateValueType};
 pub use discovery::{V1Discovery, V1DiscoveryEndpoint, V1DiscoveryServer};
 pub use electrical::{V1ACBus, V1Electrical, V1ElectricalACQualities, V1ElectricalIdentity};
 pub use environment::{
     V1Environment, V1EnvironmentCurrent, V1EnvironmentCurrentValue, V1EnvironmentDepth,
     V1EnvironmentInside, V1EnvironmentTime,
 };
-pub use full::V1FullFormat;
+pub use full::{V1FullFormat, V1PathValue};
 pub use hello::V1Hello;
-pub use navigation::{V1Navigation, V1PositionType, V1PositionValue};
+pub use history::{History, V1HistoryLimit, V1HistorySample, V1HistoryStatistics};
+pub use listeners::{Listeners, V1PathChange};
+pub use navigation::{
+    V1AttitudeType, V1AttitudeValue, V1ClosestApproach, V1ClosestApproachValue, V1Destination,
+    V1Maneuver, V1ManeuverValue, V1Navigation, V1NavigationLights, V1NavigationLightsValue,
+    V1NavigationState, V1NavigationStateValue, V1PositionType, V1PositionValue,
+};
+pub use navigation_anchor::V1Anchor;
+pub use navigation_racing::{V1Racing, V1RacingLayline};
 pub use notification::{V1Notification, V1NotificationValue};
-pub use propulsion::V1Propulsion;
+pub use priorities::{SourcePriorities, V1SourcePriority};
+pub use propulsion::{V1Propulsion, V1PropulsionDrive, V1PropulsionFuel, V1PropulsionTransmission};
 pub use put::{V1Put, V1PutValue};
+pub use registrations::{V1Registration, V1Registrations};
+pub use sails::{V1Sail, V1SailReducedState, V1Sails, V1SailsArea};
+pub use sar::V1Sar;
+pub use sensors::{V1Sensor, V1Sensors};
+pub use shared_storage::SharedStorage;
 pub use sources::{V1Source, V1SourceProperty, V1Sources};
 pub use subscribe::{V1Subscribe, V1Subscription};
+pub use tanks::{V1Tank, V1Tanks};
 pub use unsubscribe::{V1Unsubscribe, V1Unsubscription};
 pub use vessel::V1Vessel;
 
+pub mod aircraft;
+pub mod aton;
+pub mod auth;
 pub mod communication;
 pub mod definitions;
 pub mod delta;
@@ -36,16 +64,27 @@ pub mod environment;
 pub mod full;
 pub mod hello;
 mod helper_functions;
+pub mod history;
+pub mod listeners;
 pub mod navigation;
+mod navigation_anchor;
 mod navigation_course;
 mod navigation_gnss;
+mod navigation_racing;
 pub mod notification;
 mod performance;
+pub mod priorities;
 pub mod propulsion;
 pub mod put;
+pub mod registrations;
+pub mod sails;
+pub mod sar;
+pub mod sensors;
+pub mod shared_storage;
 pub mod sources;
 mod steering;
 pub mod subscribe;
+pub mod tanks;
 pub mod unsubscribe;
 pub mod vessel;
 
@@ -97,6 +136,13 @@ pub enum SignalKGetError {
 #[derive(Debug, Default)]
 pub struct Storage {
     data: V1FullFormat,
+    priorities: SourcePriorities,
+    listeners: Listeners,
+    history: History,
+    /// The last value seen by listeners of each watched leaf
+    watched_values: HashMap<(String, String), Option<serde_json::Value>>,
+    /// The meta data of each leaf that has zones
+    zone_metas: HashMap<(String, String), V1Meta>,
 }
 
 impl Storage {
@@ -126,7 +172,472 @@ impl Storage {
     ///   123.45)
     /// ```
     pub fn update(&mut self, delta: &V1DeltaFormat) {
-        self.data.apply_delta(delta);
+        self.apply_delta(delta, true);
+    }
+
+    /// Apply a delta, notifying listeners of the leaves it changes
+    ///
+    /// Without `use_priorities` every value replaces the current value.
+    fn apply_delta(&mut self, delta: &V1DeltaFormat, use_priorities: bool) {
+        let watched = self.watched_paths(delta);
+        let old_values: Vec<Option<serde_json::Value>> = watched
+            .iter()
+            .map(|key| match self.watched_values.get(key) {
+                Some(value) => value.clone(),
+                None => self.get_leaf_value(&key.0, &key.1),
+            })
+            .collect();
+        if let Some(ref context) = delta.context {
+            for meta in delta
+                .updates
+                .iter()
+                .flat_map(|update| update.meta.iter().flatten())
+            {
+                let key = (context.clone(), meta.path.clone());
+                if meta.value.zones.is_some() {
+                    self.zone_metas.insert(key, meta.value.clone());
+                } else {
+                    self.zone_metas.remove(&key);
+                }
+            }
+        }
+        let priorities = &mut self.priorities;
+        let history = &mut self.history;
+        let zone_metas = &self.zone_metas;
+        let mut numbers = Vec::new();
+        let mut anchor_watch = Vec::new();
+        let mut applied: Vec<(String, String, Option<serde_json::Value>)> = Vec::new();
+        self.data
+            .apply_delta_with(delta, |context, path, value, info| {
+                let accepted = !use_priorities || priorities.accept(context, path, info);
+                if accepted {
+                    history.record(context, path, value, info);
+                    if let Some(number) = value.as_f64() {
+                        let key = (context.to_string(), path.to_string());
+                        if zone_metas.contains_key(&key) {
+                            numbers.push((key, number, info.clone()));
+                        }
+                    }
+                    if is_anchor_watch_path(path) {
+                        anchor_watch.push((context.to_string(), path.to_string(), info.clone()));
+                    }
+                    let is_watched = watched.iter().any(|(c, p)| c == context && p == path);
+                    applied.push((
+                        context.to_string(),
+                        path.to_string(),
+                        is_watched.then(|| value.clone()),
+                    ));
+                }
+                accepted
+            });
+        let self_context = self.self_context();
+        let mut new_values: HashMap<(String, String), Option<serde_json::Value>> = HashMap::new();
+        for (context, path, value) in applied {
+            // A value for a path above or below a watched leaf changes it too
+            self.watched_values
+                .retain(|(c, p), _| *c != context || !paths_overlap(p, &path));
+            if let Some(value) = value {
+                new_values.insert((context, path), Some(value).filter(|v| !v.is_null()));
+            }
+        }
+        for (key, old_value) in watched.into_iter().zip(old_values) {
+            let Some(new_value) = new_values.remove(&key) else {
+                continue;
+            };
+            self.watched_values.insert(key.clone(), new_value.clone());
+            if new_value != old_value {
+                let change = V1PathChange::new(key.0, key.1, old_value, new_value);
+                self.listeners.notify(&self_context, &change);
+            }
+        }
+        self.evaluate_zones(numbers);
+        self.evaluate_anchor_watch(anchor_watch);
+    }
+
+    /// Raise or clear notifications for values entering or leaving the zones
+    /// in their meta data
+    ///
+    /// A value in an `alert`, `warn`, `alarm` or `emergency` zone raises a
+    /// notification under `notifications.<path>`, a value outside those zones
+    /// clears it.
+    fn evaluate_zones(&mut self, numbers: Vec<((String, String), f64, V1UpdateInfo)>) {
+        let mut deltas = Vec::new();
+        for (key, number, info) in numbers {
+            let Some(meta) = self.zone_metas.get(&key) else {
+                continue;
+            };
+            let (context, path) = key;
+            let notification_path = format!("notifications.{}", path);
+            let current_state = self
+                .data
+                .get_notification(&context, &path)
+                .map(|notification| notification.state.clone());
+            let raised = meta
+                .zone_for(number)
+                .map(|zone| {
+                    V1NotificationValue::from_zone(&path, zone, meta, info.timestamp.clone())
+                })
+                .filter(V1NotificationValue::is_active);
+            let notification = match raised {
+                Some(raised) => {
+                    if current_state.as_ref() == Some(&raised.state) {
+                        continue;
+                    }
+                    match serde_json::to_value(raised) {
+                        Ok(notification) => notification,
+                        Err(error) => {
+                            log::warn!(
+                                "Storage: Can not raise notification for {}: {}",
+                                path,
+                                error
+                            );
+                            continue;
+                        }
+                    }
+                }
+                None => {
+                    if current_state.is_none() {
+                        continue;
+                    }
+                    serde_json::Value::Null
+                }
+            };
+            let mut update = V1UpdateType::builder()
+                .add_update(V1UpdateValue::new(notification_path, notification));
+            if let Some(timestamp) = info.timestamp {
+                update = update.timestamp(timestamp);
+            }
+            deltas.push(
+                V1DeltaFormat::builder()
+                    .context(context)
+                    .add_update(update.build())
+                    .build(),
+            );
+        }
+        for delta in deltas {
+            self.apply_delta(&delta, false);
+        }
+    }
+
+    /// Keep `navigation.anchor.currentRadius` up to date with the position of
+    /// the vessel
+    ///
+    /// A vessel outside the `maxRadius` of its anchor raises an `alarm`
+    /// notification under `notifications.navigation.anchor`, it is cleared
+    /// when the vessel is back inside the circle or the anchor is lifted.
+    fn evaluate_anchor_watch(&mut self, updates: Vec<(String, String, V1UpdateInfo)>) {
+        let mut deltas = Vec::new();
+        for (context, path, info) in updates {
+            let Some(navigation) = context
+                .strip_prefix("vessels.")
+                .and_then(|id| self.data.get_vessel(id))
+                .and_then(|vessel| vessel.navigation.as_ref())
+            else {
+                continue;
+            };
+            let position = navigation
+                .position
+                .as_ref()
+                .and_then(|position| position.value.as_ref());
+            let mut update = V1UpdateType::builder();
+            let mut has_values = false;
+            if let Some(distance) = navigation.anchor_distance() {
+                update = update.add_update(V1UpdateValue::new(
+                    "navigation.anchor.currentRadius".into(),
+                    serde_json::json!(distance),
+                ));
+                has_values = true;
+            }
+            let outside = match (navigation.anchor.as_ref(), position) {
+                (Some(anchor), Some(position)) => anchor.is_outside(position),
+                _ => None,
+            }
+            .or_else(|| path.starts_with("navigation.anchor").then_some(false));
+            let current_state = self
+                .data
+                .get_notification(&context, "navigation.anchor")
+                .map(|notification| notification.state.as_str());
+            let notification = match (outside, navigation.anchor.as_ref()) {
+                (Some(true), Some(anchor)) if current_state != Some("alarm") => {
+                    let alarm = anchor.alarm(info.timestamp.clone());
+                    match serde_json::to_value(alarm) {
+                        Ok(notification) => Some(notification),
+                        Err(error) => {
+                            log::warn!("Storage: Can not raise anchor alarm: {}", error);
+                            None
+                        }
+                    }
+                }
+                (Some(false), _) if current_state.is_some() => Some(serde_json::Value::Null),
+                _ => None,
+            };
+            if let Some(notification) = notification {
+                update = update.add_update(V1UpdateValue::new(
+                    "notifications.navigation.anchor".into(),
+                    notification,
+                ));
+                has_values = true;
+            }
+            if !has_values {
+                continue;
+            }
+            if let Some(timestamp) = info.timestamp {
+                update = update.timestamp(timestamp);
+            }
+            deltas.push(
+                V1DeltaFormat::builder()
+                    .context(context)
+                    .add_update(update.build())
+                    .build(),
+            );
+        }
+        for delta in deltas {
+            self.apply_delta(&delta, false);
+        }
+    }
+
+    /// Call `callback` when an update changes a leaf matching the patterns
+    ///
+    /// Each element of the context and the path may contain `*` wildcards,
+    /// matching one level. The context `self` matches the self vessel.
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// use std::sync::{Arc, Mutex};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// let changes = Arc::new(Mutex::new(Vec::new()));
+    /// let seen = changes.clone();
+    /// storage.on_change("vessels.*", "navigation.*", move |change| {
+    ///     seen.lock().unwrap().push(change.new_value.clone());
+    /// });
+    /// let j = r#"{"updates": [
+    ///                {"values": [{"path": "navigation.courseOverGroundTrue", "value": 123.45 }]}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    /// storage.update(&delta_message);
+    /// assert_eq!(*changes.lock().unwrap(), vec![Some(serde_json::json!(123.45))]);
+    /// ```
+    pub fn on_change<F>(&mut self, context: &str, path: &str, callback: F)
+    where
+        F: FnMut(&V1PathChange) + Send + Sync + 'static,
+    {
+        self.listeners.add(context, path, Box::new(callback));
+    }
+
+    /// Get a channel receiving changes of leaves matching the patterns
+    ///
+    /// The patterns work as for `on_change`.
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// let changes = storage.subscribe("self", "navigation.courseOverGroundTrue");
+    /// let j = r#"{"updates": [
+    ///                {"values": [{"path": "navigation.courseOverGroundTrue", "value": 123.45 }]}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    /// let change = changes.try_recv().unwrap();
+    /// assert_eq!(change.old_value, None);
+    /// assert_eq!(change.new_value, Some(serde_json::json!(123.45)));
+    /// ```
+    pub fn subscribe(&mut self, context: &str, path: &str) -> Receiver<V1PathChange> {
+        let (sender, receiver) = channel();
+        self.on_change(context, path, move |change| {
+            let _ = sender.send(change.clone());
+        });
+        receiver
+    }
+
+    /// Start recording the numeric values of paths matching a pattern
+    ///
+    /// Each element of the path may contain `*` wildcards, matching one
+    /// level. The pattern applies to all contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat, V1HistoryLimit};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// storage.enable_history("environment.outside.pressure", V1HistoryLimit::Samples(60));
+    /// for (timestamp, pressure) in [
+    ///     ("2024-05-01T10:00:00.000Z", 101300.0),
+    ///     ("2024-05-01T11:00:00.000Z", 101100.0),
+    /// ] {
+    ///     let j = format!(r#"{{"context": "vessels.urn:mrn:imo:mmsi:366982330",
+    ///         "updates": [{{"timestamp": "{}",
+    ///             "values": [{{"path": "environment.outside.pressure", "value": {} }}]}}]
+    ///     }}"#, timestamp, pressure);
+    ///     let delta: V1DeltaFormat = serde_json::from_str(&j).unwrap();
+    ///     storage.update(&delta);
+    /// }
+    /// let history = storage
+    ///     .get_history("self.environment.outside.pressure", None)
+    ///     .unwrap();
+    /// assert_eq!(history.len(), 2);
+    /// let statistics = storage
+    ///     .get_history_statistics("self.environment.outside.pressure", None)
+    ///     .unwrap();
+    /// assert_eq!(statistics.mean, 101200.0);
+    /// ```
+    pub fn enable_history(&mut self, path: &str, limit: V1HistoryLimit) {
+        self.history.enable(path, limit);
+    }
+
+    /// Stop recording paths matching a pattern and forget their samples
+    pub fn disable_history(&mut self, path: &str) {
+        self.history.disable(path);
+    }
+
+    /// Return the recorded samples of a path, oldest first
+    ///
+    /// With `since` only samples from that time on are returned. Paths
+    /// without history enabled give `NoSuchPath`.
+    pub fn get_history(
+        &self,
+        path: &str,
+        since: Option<OffsetDateTime>,
+    ) -> Result<Vec<V1HistorySample>, SignalKGetError> {
+        let (context, path) = self.split_context(path)?;
+        self.history.samples(&context, &path, since)
+    }
+
+    /// Return the minimum, maximum and mean of the recorded samples of a path
+    ///
+    /// Gives `ValueNotSet` when there are no samples in the window.
+    pub fn get_history_statistics(
+        &self,
+        path: &str,
+        since: Option<OffsetDateTime>,
+    ) -> Result<V1HistoryStatistics, SignalKGetError> {
+        V1HistoryStatistics::from_samples(&self.get_history(path, since)?)
+            .ok_or(SignalKGetError::ValueNotSet)
+    }
+
+    /// Split a full path into its context and the path within the context
+    fn split_context(&self, path: &str) -> Result<(String, String), SignalKGetError> {
+        match path.split_once('.') {
+            Some(("self", rest)) => Ok((self.self_context(), rest.to_string())),
+            Some((group, rest)) => match rest.split_once('.') {
+                Some(("self", rest)) if group == "vessels" => {
+                    Ok((self.self_context(), rest.to_string()))
+                }
+                Some((id, rest)) => Ok((format!("{}.{}", group, id), rest.to_string())),
+                None => Err(SignalKGetError::NoSuchPath),
+            },
+            None => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+
+    fn self_context(&self) -> String {
+        if self.data.self_.starts_with("vessels.") {
+            self.data.self_.clone()
+        } else {
+            format!("vessels.{}", self.data.self_)
+        }
+    }
+
+    /// The context and path of every value in a delta that a listener wants
+    fn watched_paths(&self, delta: &V1DeltaFormat) -> Vec<(String, String)> {
+        let mut paths = Vec::new();
+        if self.listeners.is_empty() {
+            return paths;
+        }
+        let Some(ref context) = delta.context else {
+            return paths;
+        };
+        let self_context = self.self_context();
+        for update in &delta.updates {
+            for value in update.values.iter().flatten() {
+                let key = (context.clone(), value.path.clone());
+                if !value.path.is_empty()
+                    && !paths.contains(&key)
+                    && self.listeners.matches(&self_context, context, &value.path)
+                {
+                    paths.push(key);
+                }
+            }
+        }
+        paths
+    }
+
+    /// The value of a leaf without its timestamp, sources and meta data
+    fn get_leaf_value(&self, context: &str, path: &str) -> Option<serde_json::Value> {
+        let node = self
+            .data
+            .get_json_for_path(&format!("{}.{}", context, path))
+            .ok()?;
+        let value = match node {
+            serde_json::Value::Object(mut map) => match map.remove("value") {
+                Some(value) => value,
+                None => {
+                    for key in [
+                        "meta",
+                        "values",
+                        "timestamp",
+                        "$source",
+                        "source",
+                        "pgn",
+                        "sentence",
+                    ] {
+                        map.remove(key);
+                    }
+                    if map.is_empty() {
+                        return None;
+                    }
+                    serde_json::Value::Object(map)
+                }
+            },
+            value => value,
+        };
+        Some(value).filter(|value| !value.is_null())
+    }
+
+    /// Set the sources allowed to provide the current value of a path
+    ///
+    /// The first source in the list has the highest priority. A source with
+    /// lower priority is only used when the sources above it have been silent
+    /// for its timeout, judged by the timestamps of the updates. Values from
+    /// all sources are still kept under `values`.
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat, V1SourcePriority};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// storage.set_source_priorities(
+    ///     "navigation.speedOverGround",
+    ///     vec![
+    ///         V1SourcePriority::new("gps.GP", 0),
+    ///         V1SourcePriority::new("log.VW", 5000),
+    ///     ],
+    /// );
+    /// for (source, timestamp, sog) in [
+    ///     ("gps.GP", "2024-05-01T10:00:00.000Z", 5.6),
+    ///     ("log.VW", "2024-05-01T10:00:02.000Z", 5.1),
+    /// ] {
+    ///     let j = format!(r#"{{"context": "vessels.urn:mrn:imo:mmsi:366982330",
+    ///         "updates": [{{"$source": "{}", "timestamp": "{}",
+    ///             "values": [{{"path": "navigation.speedOverGround", "value": {} }}]}}]
+    ///     }}"#, source, timestamp, sog);
+    ///     let delta: V1DeltaFormat = serde_json::from_str(&j).unwrap();
+    ///     storage.update(&delta);
+    /// }
+    /// assert_eq!(
+    ///     storage.get_f64_for_path("self.navigation.speedOverGround".to_string()),
+    ///     Ok(5.6)
+    /// );
+    /// ```
+    pub fn set_source_priorities(&mut self, path: &str, priorities: Vec<V1SourcePriority>) {
+        self.priorities.set(path, priorities);
     }
 
     /// Get a clone of the stored data
@@ -168,7 +679,19 @@ impl Storage {
     /// assert_eq!(data, storage.get());
     /// ```
     pub fn new(data: V1FullFormat) -> Self {
-        Self { data }
+        let mut storage = Self {
+            data,
+            ..Default::default()
+        };
+        for leaf in storage.data.leaves() {
+            let Some(meta) = leaf.meta.filter(|meta| meta.zones.is_some()) else {
+                continue;
+            };
+            if let Ok(key) = storage.split_context(&leaf.path) {
+                storage.zone_metas.insert(key, meta);
+            }
+        }
+        storage
     }
 
     /// Return the f64 value stored for a SignalK path
@@ -201,15 +724,336 @@ impl Storage {
     pub fn get_f64_for_path(&self, path: String) -> Result<f64, SignalKGetError> {
         self.data.get_f64_for_path(path)
     }
+
+    /// Return the value stored for a SignalK path as any deserializable type
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1PositionValue};
+    /// let j = r#"
+    ///         { "self":"vessels.urn:mrn:imo:mmsi:366982330",
+    ///           "vessels": {
+    ///              "urn:mrn:imo:mmsi:366982330": {
+    ///                "name": "Motu",
+    ///                "navigation": {
+    ///                  "position": {
+    ///                    "value": { "latitude": 60.1, "longitude": 24.9 },
+    ///                    "timestamp": "2015-01-25T12:01:01Z",
+    ///                    "$source": "a.suitable.path"
+    ///                  }
+    ///                }
+    ///              }
+    ///           },
+    ///           "version": "1.0.0"
+    ///         }"#;
+    /// let storage = Storage::new(serde_json::from_str(j).unwrap());
+    ///
+    /// let name: String = storage.get_for_path("self.name".to_string()).unwrap();
+    /// assert_eq!(name, "Motu");
+    /// let position: V1PositionValue = storage
+    ///   .get_for_path("self.navigation.position".to_string())
+    ///   .unwrap();
+    /// assert_eq!(position.latitude, 60.1);
+    /// ```
+    pub fn get_for_path<T: DeserializeOwned + PartialEq>(
+        &self,
+        path: String,
+    ) -> Result<T, SignalKGetError> {
+        self.data.get_for_path(path)
+    }
+
+    /// Return the JSON subtree stored for a SignalK path
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::Storage;
+    /// let j = r#"
+    ///         { "self":"vessels.urn:mrn:imo:mmsi:366982330",
+    ///           "vessels": {
+    ///              "urn:mrn:imo:mmsi:366982330": {
+    ///                "electrical": {
+    ///                  "batteries": {
+    ///                    "house": { "chemistry": "LiFePO4" }
+    ///                  }
+    ///                }
+    ///              }
+    ///           },
+    ///           "version": "1.0.0"
+    ///         }"#;
+    /// let storage = Storage::new(serde_json::from_str(j).unwrap());
+    ///
+    /// assert_eq!(
+    ///   storage.get_json_for_path("self.electrical.batteries").unwrap(),
+    ///   serde_json::json!({"house": {"chemistry": "LiFePO4"}}))
+    /// ```
+    pub fn get_json_for_path(&self, path: &str) -> Result<serde_json::Value, SignalKGetError> {
+        self.data.get_json_for_path(path)
+    }
+
+    /// Return the meta data received for a SignalK path
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// let j = r#"{"updates": [
+    ///                {"values": [{"path": "navigation.speedOverGround", "value": 5.6 }],
+    ///                 "meta": [{"path": "navigation.speedOverGround",
+    ///                           "value": {"units": "m/s", "displayName": "SOG"}}]}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    /// let meta = storage.get_meta_for_path("self.navigation.speedOverGround").unwrap();
+    /// assert_eq!(meta.units, Some("m/s".to_string()));
+    /// assert_eq!(meta.display_name, Some("SOG".to_string()));
+    /// ```
+    pub fn get_meta_for_path(&self, path: &str) -> Result<V1Meta, SignalKGetError> {
+        self.data.get_meta_for_path(path)
+    }
+
+    /// Check if the value of a path is older than the timeout in its meta data
+    ///
+    /// `now` is the current time. Values without a timeout or a timestamp are
+    /// never stale.
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// use time::format_description::well_known::Rfc3339;
+    /// use time::OffsetDateTime;
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// let j = r#"{"updates": [
+    ///                {"timestamp": "2024-05-01T10:00:00.000Z",
+    ///                 "values": [{"path": "environment.depth.belowKeel", "value": 4.2 }],
+    ///                 "meta": [{"path": "environment.depth.belowKeel",
+    ///                           "value": {"units": "m", "timeout": 5}}]}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    /// let now = OffsetDateTime::parse("2024-05-01T10:00:03Z", &Rfc3339).unwrap();
+    /// assert_eq!(storage.is_stale("self.environment.depth.belowKeel", now), Ok(false));
+    /// let now = OffsetDateTime::parse("2024-05-01T10:00:06Z", &Rfc3339).unwrap();
+    /// assert_eq!(storage.is_stale("self.environment.depth.belowKeel", now), Ok(true));
+    /// ```
+    pub fn is_stale(&self, path: &str, now: OffsetDateTime) -> Result<bool, SignalKGetError> {
+        let node = self.data.get_json_for_path(path)?;
+        if node.get("value").map_or(true, serde_json::Value::is_null) {
+            return Err(SignalKGetError::ValueNotSet);
+        }
+        let timestamp = node.get("timestamp").and_then(serde_json::Value::as_str);
+        let timeout = node
+            .get("meta")
+            .and_then(|meta| meta.get("timeout"))
+            .and_then(serde_json::Value::as_f64);
+        Ok(is_stale_at(timestamp, timeout, now))
+    }
+
+    /// List the full paths of all values that are stale at `now`
+    pub fn stale_paths(&self, now: OffsetDateTime) -> Vec<String> {
+        self.stale_leaves(now)
+            .into_iter()
+            .map(|leaf| leaf.path)
+            .collect()
+    }
+
+    /// Clear all values that are stale at `now` and return their full paths
+    ///
+    /// The meta data of the paths is kept. Listeners see the values change
+    /// to `None`.
+    pub fn expire_stale_paths(&mut self, now: OffsetDateTime) -> Vec<String> {
+        let mut contexts: Vec<(String, Vec<V1PathValue>)> = Vec::new();
+        let mut expired = Vec::new();
+        for leaf in self.stale_leaves(now) {
+            expired.push(leaf.path.clone());
+            let Ok((context, path)) = self.split_context(&leaf.path) else {
+                continue;
+            };
+            let leaf = V1PathValue {
+                meta: leaf.meta,
+                ..V1PathValue::new(path, serde_json::Value::Null, None, None)
+            };
+            match contexts.iter_mut().find(|(c, _)| *c == context) {
+                Some((_, leaves)) => leaves.push(leaf),
+                None => contexts.push((context, vec![leaf])),
+            }
+        }
+        for (context, leaves) in contexts {
+            self.apply_delta(&V1DeltaFormat::from_leaves(context, leaves), false);
+        }
+        expired
+    }
+
+    fn stale_leaves(&self, now: OffsetDateTime) -> Vec<V1PathValue> {
+        self.data
+            .leaves()
+            .filter(|leaf| {
+                !leaf.value.is_null()
+                    && is_stale_at(
+                        leaf.timestamp.as_deref(),
+                        leaf.meta.as_ref().and_then(|meta| meta.timeout),
+                        now,
+                    )
+            })
+            .collect()
+    }
+
+    /// Return the raised notifications of a vessel with their paths
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// let j = r#"{"updates": [
+    ///                {"values": [{"path": "notifications.mob",
+    ///                             "value": {"method": ["visual", "sound"],
+    ///                                       "state": "emergency",
+    ///                                       "message": "Man Overboard!"}}]}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    /// let emergencies = storage.get_notifications("self", Some("emergency")).unwrap();
+    /// assert_eq!(emergencies[0].0, "mob");
+    /// assert_eq!(emergencies[0].1.message, "Man Overboard!");
+    /// assert!(storage.get_notifications("self", Some("alarm")).unwrap().is_empty());
+    /// ```
+    pub fn get_notifications(
+        &self,
+        context: &str,
+        state: Option<&str>,
+    ) -> Result<Vec<(String, V1NotificationValue)>, SignalKGetError> {
+        self.data.get_notifications(context, state)
+    }
+
+    /// Return the value of a SignalK path from every source that reported it
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::{Storage, V1DeltaFormat, V1FullFormat};
+    /// let mut storage = Storage::new(V1FullFormat::default());
+    /// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+    /// let j = r#"{"updates": [
+    ///                {"values": [{"path": "navigation.headingMagnetic", "value": 1.55 }],
+    ///                 "$source": "nmea1.HC", "timestamp": "2024-05-01T10:00:00.000Z"},
+    ///                {"values": [{"path": "navigation.headingMagnetic", "value": 1.57 }],
+    ///                 "$source": "n2k.204", "timestamp": "2024-05-01T10:00:00.100Z"}
+    ///             ],
+    ///             "context": "vessels.urn:mrn:imo:mmsi:366982330"
+    /// }"#;
+    /// let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+    /// storage.update(&delta_message);
+    ///
+    /// let values = storage.get_values_for_path("self.navigation.headingMagnetic").unwrap();
+    /// assert_eq!(values.len(), 2);
+    /// assert_eq!(values["nmea1.HC"].value, Some(serde_json::json!(1.55)));
+    ///
+    /// let (source, value) = storage
+    ///     .get_preferred_value_for_path("self.navigation.headingMagnetic", &["nmea1.HC"])
+    ///     .unwrap();
+    /// assert_eq!(source, "nmea1.HC");
+    /// assert_eq!(value.value, Some(serde_json::json!(1.55)));
+    /// ```
+    pub fn get_values_for_path(
+        &self,
+        path: &str,
+    ) -> Result<HashMap<String, V1SourceValue>, SignalKGetError> {
+        self.data.get_values_for_path(path)
+    }
+
+    /// Return the value of a SignalK path from the first preferred source that reported it
+    ///
+    /// The current value is returned when none of the preferred sources has
+    /// reported the path.
+    pub fn get_preferred_value_for_path(
+        &self,
+        path: &str,
+        preferred: &[&str],
+    ) -> Result<(String, V1SourceValue), SignalKGetError> {
+        self.data.get_preferred_value_for_path(path, preferred)
+    }
+
+    /// Return every `(context, path, value)` matching a wildcard context and path
+    ///
+    /// # Examples
+    /// ```
+    /// use signalk::Storage;
+    /// let j = r#"
+    ///         { "self":"vessels.urn:mrn:imo:mmsi:366982330",
+    ///           "vessels": {
+    ///              "urn:mrn:imo:mmsi:366982330": {
+    ///                "navigation": {
+    ///                  "speedOverGround": {
+    ///                    "value": 5.1, "timestamp": "2015-01-25T12:01:01Z", "$source": "a.path"
+    ///                  }
+    ///                }
+    ///              },
+    ///              "urn:mrn:imo:mmsi:230099999": {
+    ///                "navigation": {
+    ///                  "speedOverGround": {
+    ///                    "value": 7.2, "timestamp": "2015-01-25T12:01:01Z", "$source": "b.path"
+    ///                  }
+    ///                }
+    ///              }
+    ///           },
+    ///           "version": "1.0.0"
+    ///         }"#;
+    /// let storage = Storage::new(serde_json::from_str(j).unwrap());
+    ///
+    /// let speeds = storage.query("vessels.*", "navigation.speedOverGround");
+    /// assert_eq!(speeds.len(), 2);
+    /// assert_eq!(speeds[0].0, "vessels.urn:mrn:imo:mmsi:230099999");
+    /// assert_eq!(speeds[0].1, "navigation.speedOverGround");
+    /// assert_eq!(speeds[0].2, 7.2);
+    /// ```
+    pub fn query(&self, context: &str, path: &str) -> Vec<(String, String, serde_json::Value)> {
+        self.data.query(context, path)
+    }
+}
+
+/// Check if a value with a timestamp and a timeout in seconds is stale at `now`
+fn is_stale_at(timestamp: Option<&str>, timeout: Option<f64>, now: OffsetDateTime) -> bool {
+    let (Some(timestamp), Some(timeout)) = (timestamp, timeout) else {
+        return false;
+    };
+    match OffsetDateTime::parse(timestamp, &Rfc3339) {
+        Ok(time) => now - time > Duration::seconds_f64(timeout),
+        Err(_) => false,
+    }
+}
+
+/// Check if one path is the other or contains it
+fn paths_overlap(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short.is_empty()
+        || long == short
+        || (long.starts_with(short) && long.as_bytes()[short.len()] == b'.')
+}
+
+/// Check if a path moves the vessel or its anchor, `currentRadius` is left out
+/// as it is the result of the anchor watch
+fn is_anchor_watch_path(path: &str) -> bool {
+    path == "navigation.position"
+        || (path.starts_with("navigation.anchor") && path != "navigation.anchor.currentRadius")
 }
 
 #[cfg(test)]
 mod storage_tests {
-    use serde_json::{Number, Value};
+    use serde_json::{json, Value};
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
 
     use crate::{
-        Storage, V1DeltaFormat, V1FullFormat, V1Navigation, V1NumberValue, V1UpdateType,
-        V1UpdateValue, V1Vessel,
+        SignalKGetError, Storage, V1DefSource, V1DeltaFormat, V1FullFormat, V1HistoryLimit, V1Meta,
+        V1MetaZone, V1Navigation, V1NotificationValue, V1NumberValue, V1PathChange,
+        V1SourcePriority, V1UpdateMeta, V1UpdateType, V1UpdateValue, V1Vessel,
     };
 
     #[test]
@@ -235,18 +1079,11 @@ mod storage_tests {
                     .build(),
             )
             .build();
-        let delta = V1DeltaFormat::builder()
-            .context("vessels.urn:mrn:imo:mmsi:366982330".into())
-            .add_update(
-                V1UpdateType::builder()
-                    .add_update(V1UpdateValue::new(
-                        "navigation.speedOverGround".into(),
-                        Value::Number(Number::from_f64(5.6).unwrap()),
-                    ))
-                    .build(),
-            )
-            .build();
-        storage.update(&delta);
+        storage.update(&delta(
+            VESSEL,
+            None,
+            vec![("navigation.speedOverGround", json!(5.6))],
+        ));
         assert_eq!(expected, storage.get())
     }
 
@@ -266,18 +1103,749 @@ mod storage_tests {
                     .build(),
             )
             .build();
-        let delta = V1DeltaFormat::builder()
-            .context("vessels.urn:mrn:imo:mmsi:366982330".into())
-            .add_update(
-                V1UpdateType::builder()
-                    .add_update(V1UpdateValue::new(
-                        "navigation.speedOverGround".into(),
-                        Value::Number(Number::from_f64(15.8).unwrap()),
-                    ))
+        storage.update(&delta(
+            VESSEL,
+            None,
+            vec![("navigation.speedOverGround", json!(15.8))],
+        ));
+        assert_eq!(expected, storage.get())
+    }
+
+    const VESSEL: &str = "vessels.urn:mrn:imo:mmsi:366982330";
+
+    /// A delta with one update holding `values`
+    fn delta(context: &str, timestamp: Option<&str>, values: Vec<(&str, Value)>) -> V1DeltaFormat {
+        let mut update = V1UpdateType::builder();
+        for (path, value) in values {
+            update = update.add_update(V1UpdateValue::new(path.into(), value));
+        }
+        if let Some(timestamp) = timestamp {
+            update = update.timestamp(timestamp.into());
+        }
+        V1DeltaFormat::builder()
+            .context(context.into())
+            .add_update(update.build())
+            .build()
+    }
+
+    /// Add meta data for a path to the update of a delta
+    fn with_meta(mut delta: V1DeltaFormat, path: &str, meta: V1Meta) -> V1DeltaFormat {
+        delta.updates[0]
+            .meta
+            .get_or_insert_with(Vec::new)
+            .push(V1UpdateMeta::new(path.into(), meta));
+        delta
+    }
+
+    fn depth_meta() -> V1Meta {
+        V1Meta::builder()
+            .units("m".into())
+            .display_name("Depth".into())
+            .zones(
+                V1MetaZone::builder()
+                    .upper(2.0)
+                    .state("alarm".into())
                     .build(),
             )
+            .build()
+    }
+
+    fn depth_delta(depth: Option<f64>, meta: Option<V1Meta>) -> V1DeltaFormat {
+        let values = depth.map(|depth| ("environment.depth.belowKeel", json!(depth)));
+        let delta = delta(VESSEL, None, values.into_iter().collect());
+        match meta {
+            Some(meta) => with_meta(delta, "environment.depth.belowKeel", meta),
+            None => delta,
+        }
+    }
+
+    #[test]
+    fn apply_delta_with_meta() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&depth_delta(Some(4.2), Some(depth_meta())));
+        assert_eq!(
+            storage
+                .get_f64_for_path("self.environment.depth.belowKeel".into())
+                .unwrap(),
+            4.2
+        );
+        assert_eq!(
+            storage
+                .get_meta_for_path("self.environment.depth.belowKeel")
+                .unwrap(),
+            depth_meta()
+        );
+    }
+
+    #[test]
+    fn meta_is_kept_when_value_updates() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&depth_delta(None, Some(depth_meta())));
+        assert_eq!(
+            storage.get_f64_for_path("self.environment.depth.belowKeel".into()),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        storage.update(&depth_delta(Some(1.5), None));
+        assert_eq!(
+            storage
+                .get_f64_for_path("self.environment.depth.belowKeel".into())
+                .unwrap(),
+            1.5
+        );
+        assert_eq!(
+            storage
+                .get_meta_for_path("self.environment.depth.belowKeel")
+                .unwrap(),
+            depth_meta()
+        );
+    }
+
+    #[test]
+    fn get_meta_for_path_without_meta() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&depth_delta(Some(4.2), None));
+        assert_eq!(
+            storage.get_meta_for_path("self.environment.depth.belowKeel"),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            storage.get_meta_for_path("self.environment.depth.belowSurface"),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            storage.get_meta_for_path("self.environment.noSuchGroup"),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn apply_delta_records_timestamp_and_source() {
+        let mut storage = Storage::default();
+        let source = V1DefSource::builder()
+            .label("n2k".into())
+            .type_("NMEA2000".into())
+            .src("115".into())
+            .pgn(128259)
             .build();
+        let mut delta = delta(
+            VESSEL,
+            Some("2024-05-01T10:00:00.000Z"),
+            vec![("navigation.speedOverGround", json!(5.6))],
+        );
+        delta.updates[0].source = Some(source.clone());
         storage.update(&delta);
+        let expected = V1FullFormat::builder()
+            .add_vessel(
+                "urn:mrn:imo:mmsi:366982330".into(),
+                V1Vessel::builder()
+                    .mmsi("366982330".into())
+                    .navigation(
+                        V1Navigation::builder()
+                            .speed_over_ground(
+                                V1NumberValue::builder()
+                                    .value(5.6)
+                                    .timestamp("2024-05-01T10:00:00.000Z".into())
+                                    .source("n2k.115".into())
+                                    .pgn(128259.0)
+                                    .def_source(source)
+                                    .build(),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
         assert_eq!(expected, storage.get())
     }
+
+    #[test]
+    fn later_update_replaces_timestamp_and_source() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        for (source, timestamp, depth) in [
+            ("nmea1.II", "2024-05-01T10:00:00.000Z", 4.2),
+            ("nmea2.SD", "2024-05-01T10:00:01.000Z", 4.3),
+        ] {
+            let mut delta = delta(
+                VESSEL,
+                Some(timestamp),
+                vec![("environment.depth.belowKeel", json!(depth))],
+            );
+            delta.updates[0].ref_source = Some(source.into());
+            storage.update(&delta);
+        }
+        assert_eq!(
+            storage
+                .get_json_for_path("self.environment.depth.belowKeel")
+                .unwrap(),
+            serde_json::json!({
+                "value": 4.3,
+                "timestamp": "2024-05-01T10:00:01.000Z",
+                "$source": "nmea2.SD",
+                "values": {
+                    "nmea1.II": {"value": 4.2, "timestamp": "2024-05-01T10:00:00.000Z"},
+                    "nmea2.SD": {"value": 4.3, "timestamp": "2024-05-01T10:00:01.000Z"}
+                }
+            })
+        );
+    }
+
+    fn sog_delta(source: &str, timestamp: &str, sog: f64) -> V1DeltaFormat {
+        let mut delta = delta(
+            VESSEL,
+            Some(timestamp),
+            vec![("navigation.speedOverGround", json!(sog))],
+        );
+        delta.updates[0].ref_source = Some(source.into());
+        delta
+    }
+
+    fn storage_with_sog_priorities() -> Storage {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.set_source_priorities(
+            "navigation.speedOverGround",
+            vec![
+                V1SourcePriority::new("gps.GP", 0),
+                V1SourcePriority::new("log.VW", 5000),
+            ],
+        );
+        storage
+    }
+
+    fn sog_and_source(storage: &Storage) -> (f64, String) {
+        let sog = storage
+            .get_f64_for_path("self.navigation.speedOverGround".into())
+            .unwrap();
+        let source = storage
+            .get_preferred_value_for_path("self.navigation.speedOverGround", &[])
+            .unwrap()
+            .0;
+        (sog, source)
+    }
+
+    #[test]
+    fn lower_priority_source_is_only_kept_in_values() {
+        let mut storage = storage_with_sog_priorities();
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:00.000Z", 5.6));
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:02.000Z", 5.1));
+        assert_eq!(sog_and_source(&storage), (5.6, "gps.GP".to_string()));
+        let values = storage
+            .get_values_for_path("self.navigation.speedOverGround")
+            .unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["log.VW"].value, Some(serde_json::json!(5.1)));
+    }
+
+    #[test]
+    fn lower_priority_source_takes_over_when_primary_is_stale() {
+        let mut storage = storage_with_sog_priorities();
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:00.000Z", 5.6));
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:06.000Z", 5.1));
+        assert_eq!(sog_and_source(&storage), (5.1, "log.VW".to_string()));
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:07.000Z", 5.2));
+        assert_eq!(sog_and_source(&storage), (5.2, "log.VW".to_string()));
+    }
+
+    #[test]
+    fn higher_priority_source_takes_back_the_path() {
+        let mut storage = storage_with_sog_priorities();
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:00.000Z", 5.1));
+        assert_eq!(sog_and_source(&storage), (5.1, "log.VW".to_string()));
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:01.000Z", 5.6));
+        assert_eq!(sog_and_source(&storage), (5.6, "gps.GP".to_string()));
+    }
+
+    #[test]
+    fn unlisted_source_uses_longest_timeout() {
+        let mut storage = storage_with_sog_priorities();
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:00.000Z", 5.6));
+        storage.update(&sog_delta("other.XX", "2024-05-01T10:00:04.000Z", 4.0));
+        assert_eq!(sog_and_source(&storage), (5.6, "gps.GP".to_string()));
+        storage.update(&sog_delta("other.XX", "2024-05-01T10:00:06.000Z", 4.1));
+        assert_eq!(sog_and_source(&storage), (4.1, "other.XX".to_string()));
+    }
+
+    #[test]
+    fn paths_without_priorities_take_every_update() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:00.000Z", 5.6));
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:01.000Z", 5.1));
+        assert_eq!(sog_and_source(&storage), (5.1, "log.VW".to_string()));
+    }
+
+    fn depth_and_sog_delta(depth: Option<f64>, sog: f64) -> V1DeltaFormat {
+        delta(
+            VESSEL,
+            None,
+            vec![
+                ("environment.depth.belowKeel", json!(depth)),
+                ("navigation.speedOverGround", json!(sog)),
+            ],
+        )
+    }
+
+    #[test]
+    fn listener_gets_old_and_new_value() {
+        let mut storage = Storage::default();
+        let changes = storage.subscribe("vessels.*", "environment.depth.*");
+        storage.update(&depth_and_sog_delta(Some(4.2), 5.6));
+        storage.update(&depth_and_sog_delta(Some(4.3), 5.7));
+        let received: Vec<V1PathChange> = changes.try_iter().collect();
+        assert_eq!(
+            received,
+            vec![
+                V1PathChange::new(
+                    "vessels.urn:mrn:imo:mmsi:366982330".into(),
+                    "environment.depth.belowKeel".into(),
+                    None,
+                    Some(serde_json::json!(4.2)),
+                ),
+                V1PathChange::new(
+                    "vessels.urn:mrn:imo:mmsi:366982330".into(),
+                    "environment.depth.belowKeel".into(),
+                    Some(serde_json::json!(4.2)),
+                    Some(serde_json::json!(4.3)),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn listener_is_not_called_for_unchanged_value() {
+        let mut storage = Storage::default();
+        let changes = storage.subscribe("vessels.*", "navigation.speedOverGround");
+        storage.update(&depth_and_sog_delta(Some(4.2), 5.6));
+        storage.update(&depth_and_sog_delta(Some(4.3), 5.6));
+        assert_eq!(changes.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn listener_sees_cleared_value() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let changes = storage.subscribe("self", "environment.depth.belowKeel");
+        storage.update(&depth_and_sog_delta(Some(4.2), 5.6));
+        storage.update(&depth_and_sog_delta(None, 5.6));
+        let last = changes.try_iter().last().unwrap();
+        assert_eq!(last.old_value, Some(serde_json::json!(4.2)));
+        assert_eq!(last.new_value, None);
+    }
+
+    #[test]
+    fn listener_sees_value_changed_through_parent_path() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let changes = storage.subscribe("self", "name");
+        for (path, value) in [
+            ("name", serde_json::json!("Motu")),
+            ("", serde_json::json!({"name": "Kiwi"})),
+            ("name", serde_json::json!("Kiwi")),
+            ("name", serde_json::json!("Tui")),
+        ] {
+            storage.update(&delta(VESSEL, None, vec![(path, value)]));
+        }
+        let received: Vec<(Option<Value>, Option<Value>)> = changes
+            .try_iter()
+            .map(|change| (change.old_value, change.new_value))
+            .collect();
+        assert_eq!(
+            received,
+            vec![
+                (None, Some(serde_json::json!("Motu"))),
+                (
+                    Some(serde_json::json!("Kiwi")),
+                    Some(serde_json::json!("Tui"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn listener_for_other_context_is_not_called() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:123456789");
+        let changes = storage.subscribe("self", "*.*");
+        storage.update(&depth_and_sog_delta(Some(4.2), 5.6));
+        assert_eq!(changes.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn history_only_records_accepted_values() {
+        let mut storage = storage_with_sog_priorities();
+        storage.enable_history("navigation.speedOverGround", V1HistoryLimit::Samples(10));
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:00.000Z", 5.6));
+        storage.update(&sog_delta("log.VW", "2024-05-01T10:00:01.000Z", 5.1));
+        storage.update(&sog_delta("gps.GP", "2024-05-01T10:00:02.000Z", 5.8));
+        let samples: Vec<f64> = storage
+            .get_history("self.navigation.speedOverGround", None)
+            .unwrap()
+            .into_iter()
+            .map(|sample| sample.value)
+            .collect();
+        assert_eq!(samples, vec![5.6, 5.8]);
+        assert_eq!(
+            storage
+                .get_history_statistics(
+                    "vessels.urn:mrn:imo:mmsi:366982330.navigation.speedOverGround",
+                    None
+                )
+                .unwrap()
+                .max,
+            5.8
+        );
+        assert_eq!(
+            storage.get_history("self.environment.depth.belowKeel", None),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    fn timed_delta(timestamp: &str, depth: f64, sog: f64) -> V1DeltaFormat {
+        let delta = delta(
+            VESSEL,
+            Some(timestamp),
+            vec![
+                ("environment.depth.belowKeel", json!(depth)),
+                ("navigation.speedOverGround", json!(sog)),
+            ],
+        );
+        let meta = V1Meta::builder().units("m".into()).timeout(5.0).build();
+        with_meta(delta, "environment.depth.belowKeel", meta)
+    }
+
+    fn time(timestamp: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(timestamp, &Rfc3339).unwrap()
+    }
+
+    #[test]
+    fn only_values_with_timeout_get_stale() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&timed_delta("2024-05-01T10:00:00.000Z", 4.2, 5.6));
+        let now = time("2024-05-01T10:01:00Z");
+        assert_eq!(
+            storage.stale_paths(now),
+            vec!["vessels.urn:mrn:imo:mmsi:366982330.environment.depth.belowKeel".to_string()]
+        );
+        assert_eq!(
+            storage.is_stale("self.navigation.speedOverGround", now),
+            Ok(false)
+        );
+        assert_eq!(
+            storage.stale_paths(time("2024-05-01T10:00:05Z")),
+            Vec::<String>::new()
+        );
+        storage.update(&timed_delta("2024-05-01T10:00:58.000Z", 4.3, 5.6));
+        assert_eq!(storage.stale_paths(now), Vec::<String>::new());
+    }
+
+    #[test]
+    fn expire_stale_paths_clears_value_and_keeps_meta() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let changes = storage.subscribe("self", "environment.depth.belowKeel");
+        storage.update(&timed_delta("2024-05-01T10:00:00.000Z", 4.2, 5.6));
+        let now = time("2024-05-01T10:01:00Z");
+        assert_eq!(
+            storage.expire_stale_paths(now),
+            vec!["vessels.urn:mrn:imo:mmsi:366982330.environment.depth.belowKeel".to_string()]
+        );
+        assert_eq!(
+            storage.is_stale("self.environment.depth.belowKeel", now),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            storage
+                .get_meta_for_path("self.environment.depth.belowKeel")
+                .unwrap()
+                .timeout,
+            Some(5.0)
+        );
+        assert_eq!(
+            storage.get_f64_for_path("self.navigation.speedOverGround".into()),
+            Ok(5.6)
+        );
+        assert_eq!(changes.try_iter().last().unwrap().new_value, None);
+        assert_eq!(storage.expire_stale_paths(now), Vec::<String>::new());
+    }
+
+    fn depth_zones_meta() -> V1Meta {
+        V1Meta::builder()
+            .display_name("Depth".into())
+            .zones(
+                V1MetaZone::builder()
+                    .upper(1.5)
+                    .state("alarm".into())
+                    .message("Running aground".into())
+                    .build(),
+            )
+            .zones(
+                V1MetaZone::builder()
+                    .lower(1.5)
+                    .upper(3.0)
+                    .state("warn".into())
+                    .build(),
+            )
+            .alarm_method("sound".into())
+            .build()
+    }
+
+    fn depth_zones_delta(depth: f64, zones: bool) -> V1DeltaFormat {
+        let delta = delta(
+            VESSEL,
+            Some("2024-05-01T10:00:00.000Z"),
+            vec![("environment.depth.belowKeel", json!(depth))],
+        );
+        if zones {
+            with_meta(delta, "environment.depth.belowKeel", depth_zones_meta())
+        } else {
+            delta
+        }
+    }
+
+    #[test]
+    fn value_in_zone_raises_notification() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&depth_zones_delta(1.2, true));
+        assert_eq!(
+            storage.get_for_path::<V1NotificationValue>(
+                "self.notifications.environment.depth.belowKeel".into()
+            ),
+            Ok(V1NotificationValue::builder()
+                .method("sound".into())
+                .state("alarm".into())
+                .message("Running aground".into())
+                .timestamp("2024-05-01T10:00:00.000Z".into())
+                .build())
+        );
+        storage.update(&depth_zones_delta(2.0, false));
+        assert_eq!(
+            storage.get_for_path::<V1NotificationValue>(
+                "self.notifications.environment.depth.belowKeel".into()
+            ),
+            Ok(V1NotificationValue::builder()
+                .method("visual".into())
+                .state("warn".into())
+                .message("Depth is in warn zone".into())
+                .timestamp("2024-05-01T10:00:00.000Z".into())
+                .build())
+        );
+    }
+
+    #[test]
+    fn value_leaving_zones_clears_notification() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let changes = storage.subscribe("self", "notifications.environment.depth.belowKeel");
+        storage.update(&depth_zones_delta(1.2, true));
+        storage.update(&depth_zones_delta(1.1, false));
+        storage.update(&depth_zones_delta(5.0, false));
+        storage.update(&depth_zones_delta(6.0, false));
+        let received: Vec<bool> = changes
+            .try_iter()
+            .map(|change| change.new_value.is_some())
+            .collect();
+        assert_eq!(received, vec![true, false]);
+        assert_eq!(storage.data().get_self().unwrap().notifications, None);
+    }
+
+    #[test]
+    fn values_without_zones_raise_nothing() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&depth_zones_delta(1.2, false));
+        assert_eq!(storage.data().get_self().unwrap().notifications, None);
+    }
+
+    #[test]
+    fn zones_in_loaded_data_raise_notification() {
+        let mut first = Storage::default();
+        first.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        first.update(&depth_zones_delta(5.0, true));
+        let mut storage = Storage::new(first.get());
+        storage.update(&depth_zones_delta(1.2, false));
+        let alarms = storage.get_notifications("self", Some("alarm")).unwrap();
+        assert_eq!(alarms[0].0, "environment.depth.belowKeel");
+        assert_eq!(alarms[0].1.message, "Running aground");
+    }
+
+    fn read_delta(name: &str) -> V1DeltaFormat {
+        let folder = std::path::Path::new("tests/specification/examples/delta/");
+        let file = std::fs::File::open(folder.join(name)).unwrap();
+        serde_json::from_reader(std::io::BufReader::new(file)).unwrap()
+    }
+
+    #[test]
+    fn notification_deltas_are_applied() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:signalk:uuid:c0d79334-4e25-4245-8892-54e8ccc8021d");
+        storage.update(&read_delta("MOB-alarm-delta.json"));
+        let mut anchor = delta(
+            "vessels.urn:mrn:signalk:uuid:c0d79334-4e25-4245-8892-54e8ccc8021d",
+            None,
+            vec![(
+                "notifications.navigation.anchor",
+                json!({"method": ["sound"], "state": "alarm", "message": "Dragging anchor!"}),
+            )],
+        );
+        anchor.updates[0].ref_source = Some("anchoralarm".into());
+        storage.update(&anchor);
+        let active: Vec<(String, String)> = storage
+            .get_notifications("self", None)
+            .unwrap()
+            .into_iter()
+            .map(|(path, notification)| (path, notification.state))
+            .collect();
+        assert_eq!(
+            active,
+            vec![
+                ("mob".to_string(), "emergency".to_string()),
+                ("navigation.anchor".to_string(), "alarm".to_string()),
+            ]
+        );
+        assert_eq!(
+            storage
+                .get_json_for_path("self.notifications.mob")
+                .unwrap()
+                .get("timestamp"),
+            Some(&serde_json::json!("2014-08-15T16:00:05.538Z"))
+        );
+        storage.update(&read_delta("docs-notifications.json"));
+        assert_eq!(
+            storage.get_notifications("self", Some("emergency")),
+            Ok(vec![])
+        );
+        assert_eq!(
+            storage.get_notifications("vessels.unknown", None),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    fn navigation_delta(path: &str, value: Value) -> V1DeltaFormat {
+        let mut delta = delta(VESSEL, Some("2024-06-01T02:00:00Z"), vec![(path, value)]);
+        delta.updates[0].ref_source = Some("gps.1".into());
+        delta
+    }
+
+    fn anchor_state(storage: &Storage) -> Option<String> {
+        storage
+            .get_notifications("self", None)
+            .unwrap()
+            .into_iter()
+            .find(|(path, _)| path == "navigation.anchor")
+            .map(|(_, notification)| notification.state)
+    }
+
+    #[test]
+    fn anchor_watch_follows_position() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let position =
+            |latitude: f64, longitude: f64| json!({"latitude": latitude, "longitude": longitude});
+        storage.update(&navigation_delta(
+            "navigation.position",
+            position(59.0, 18.0),
+        ));
+        storage.update(&navigation_delta(
+            "navigation.anchor.position",
+            position(59.0, 18.0),
+        ));
+        storage.update(&navigation_delta(
+            "navigation.anchor.maxRadius",
+            json!(50.0),
+        ));
+        assert_eq!(
+            storage.get_f64_for_path("self.navigation.anchor.currentRadius".into()),
+            Ok(0.0)
+        );
+        storage.update(&navigation_delta(
+            "navigation.position",
+            position(59.0003, 18.0),
+        ));
+        let radius = storage
+            .get_f64_for_path("self.navigation.anchor.currentRadius".into())
+            .unwrap();
+        assert!((radius - 33.36).abs() < 0.1, "{}", radius);
+        assert_eq!(anchor_state(&storage), None);
+
+        storage.update(&navigation_delta(
+            "navigation.position",
+            position(59.0, 18.001),
+        ));
+        assert_eq!(anchor_state(&storage), Some("alarm".to_string()));
+        assert_eq!(
+            storage
+                .get_json_for_path("self.notifications.navigation.anchor.value.timestamp")
+                .unwrap(),
+            json!("2024-06-01T02:00:00Z")
+        );
+
+        storage.update(&navigation_delta(
+            "navigation.position",
+            position(59.0002, 18.0),
+        ));
+        assert_eq!(anchor_state(&storage), None);
+
+        storage.update(&navigation_delta(
+            "navigation.position",
+            position(59.0, 18.001),
+        ));
+        assert_eq!(anchor_state(&storage), Some("alarm".to_string()));
+        storage.update(&navigation_delta(
+            "navigation.anchor.position",
+            serde_json::Value::Null,
+        ));
+        assert_eq!(anchor_state(&storage), None);
+    }
+
+    #[test]
+    fn anchor_alarm_uses_anchor_meta() {
+        let mut storage = Storage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let meta = V1Meta::builder()
+            .alarm_method("visual".into())
+            .zones(
+                V1MetaZone::builder()
+                    .state("alarm".into())
+                    .message("Dragging anchor!".into())
+                    .build(),
+            )
+            .build();
+        storage.update(&with_meta(
+            delta(VESSEL, None, vec![]),
+            "navigation.anchor",
+            meta,
+        ));
+        for (path, value) in [
+            (
+                "navigation.anchor.position",
+                serde_json::json!({"latitude": 59.0, "longitude": 18.0}),
+            ),
+            ("navigation.anchor.maxRadius", serde_json::json!(50.0)),
+            (
+                "navigation.position",
+                serde_json::json!({"latitude": 59.0, "longitude": 18.001}),
+            ),
+        ] {
+            storage.update(&navigation_delta(path, value));
+        }
+        let alarms = storage.get_notifications("self", Some("alarm")).unwrap();
+        assert_eq!(
+            alarms,
+            vec![(
+                "navigation.anchor".to_string(),
+                V1NotificationValue::builder()
+                    .method("visual".into())
+                    .state("alarm".into())
+                    .message("Dragging anchor!".into())
+                    .timestamp("2024-06-01T02:00:00Z".into())
+                    .build()
+            )]
+        );
+    }
 }
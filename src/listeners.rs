@@ -0,0 +1,103 @@
+use serde_json::Value;
+use std::fmt;
+
+use crate::helper_functions::matches_path_pattern;
+
+/// A change of a leaf made by `Storage::update`
+///
+/// `old_value` is `None` for a new leaf and `new_value` is `None` for a
+/// leaf that was cleared.
+#[derive(PartialEq, Debug, Clone)]
+pub struct V1PathChange {
+    pub context: String,
+    pub path: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+impl V1PathChange {
+    pub fn new(
+        context: String,
+        path: String,
+        old_value: Option<Value>,
+        new_value: Option<Value>,
+    ) -> Self {
+        Self {
+            context,
+            path,
+            old_value,
+            new_value,
+        }
+    }
+}
+
+type Callback = Box<dyn FnMut(&V1PathChange) + Send + Sync>;
+
+struct Listener {
+    context: String,
+    path: String,
+    callback: Callback,
+}
+
+/// Callbacks registered for a context and a path pattern
+///
+/// Each element of the patterns may contain `*` wildcards and matches exactly
+/// one level, e.g. `vessels.*` with `electrical.batteries.*.voltage`.
+#[derive(Default)]
+pub struct Listeners {
+    listeners: Vec<Listener>,
+}
+
+impl Listeners {
+    pub fn add(&mut self, context: &str, path: &str, callback: Callback) {
+        self.listeners.push(Listener {
+            context: context.to_string(),
+            path: path.to_string(),
+            callback,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// Check if any listener wants changes of a path
+    ///
+    /// `self_context` is the context the pattern `self` stands for.
+    pub fn matches(&self, self_context: &str, context: &str, path: &str) -> bool {
+        self.listeners
+            .iter()
+            .any(|listener| listener.matches(self_context, context, path))
+    }
+
+    /// Call every listener matching the change
+    pub fn notify(&mut self, self_context: &str, change: &V1PathChange) {
+        for listener in self.listeners.iter_mut() {
+            if listener.matches(self_context, &change.context, &change.path) {
+                (listener.callback)(change);
+            }
+        }
+    }
+}
+
+impl Listener {
+    fn matches(&self, self_context: &str, context: &str, path: &str) -> bool {
+        let context_matches = match self.context.as_str() {
+            "self" | "vessels.self" => context == self_context,
+            pattern => matches_path_pattern(pattern, context),
+        };
+        context_matches && matches_path_pattern(&self.path, path)
+    }
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.listeners
+                    .iter()
+                    .map(|listener| (&listener.context, &listener.path)),
+            )
+            .finish()
+    }
+}
@@ -1,34 +1,43 @@
-use crate::definitions::{V1DateTime, V1NumberValue, V1StringValue};
-use crate::helper_functions::{get_f64_value, get_path, F64CompatiblePath, Path};
+use crate::definitions::{
+    add_source_value, update_value, UpdatableValue, V1CommonValueFields, V1DateTime, V1DefSource,
+    V1Meta, V1NumberValue, V1SourceValue, V1StringValue,
+};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{
+    get_f64_value, get_f64_value_for_path, get_path, last_key_wins, F64CompatiblePath, Path,
+};
+use crate::navigation_anchor::V1Anchor;
 use crate::navigation_course::{V1Course, V1CourseApi};
 use crate::navigation_gnss::V1gnss;
+use crate::navigation_racing::V1Racing;
 use crate::SignalKGetError;
 use log::debug;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1Navigation {
-    // pub lights: Option<V1Lights>,
+    pub lights: Option<V1NavigationLights>,
     pub course_over_ground_magnetic: Option<V1NumberValue>,
     pub course_over_ground_true: Option<V1NumberValue>,
     pub course: Option<V1CourseApi>,
     pub course_rhumbline: Option<V1Course>,
     pub course_great_circle: Option<V1Course>,
-    // pub closest_approach: Option<V1ClosestApproach>,
-    // pub racing: Option<V1Racing>,
+    pub closest_approach: Option<V1ClosestApproach>,
+    pub racing: Option<V1Racing>,
     pub magnetic_variation: Option<V1NumberValue>,
     pub magnetic_variation_age_of_service: Option<V1NumberValue>,
-    // pub destination: Option<V1Destination>,
+    pub destination: Option<V1Destination>,
     pub gnss: Option<V1gnss>,
     pub heading_magnetic: Option<V1NumberValue>,
     pub magnetic_deviation: Option<V1NumberValue>,
     pub heading_compass: Option<V1NumberValue>,
     pub heading_true: Option<V1NumberValue>,
     pub position: Option<V1PositionType>,
-    // pub attitude: Option<V1AttitudeType>,
-    // pub maneuver: Option<V1Maneuver>,
+    pub attitude: Option<V1AttitudeType>,
+    pub maneuver: Option<V1Maneuver>,
     pub rate_of_turn: Option<V1NumberValue>,
     pub speed_over_ground: Option<V1NumberValue>,
     pub speed_through_water: Option<V1NumberValue>,
@@ -38,8 +47,9 @@ pub struct V1Navigation {
     pub leeway_angle: Option<V1NumberValue>,
     pub log: Option<V1NumberValue>,
     pub trip: Option<V1Trip>,
-    // pub state: Option<V1State>,
-    // pub anchor: Option<V1Anchor>,
+    pub state: Option<V1NavigationState>,
+    #[serde(default, deserialize_with = "last_key_wins")]
+    pub anchor: Option<V1Anchor>,
     pub datetime: Option<V1DateTime>,
 }
 
@@ -47,27 +57,27 @@ impl Path<f64> for V1Navigation {
     fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
         debug!("get_path({:?}): ...", path);
         match path[0] {
-            "lights" => Err(SignalKGetError::TBD),
+            "lights" => Err(SignalKGetError::WrongDataType),
             "courseOverGroundMagnetic" => get_f64_value(&self.course_over_ground_magnetic),
             "courseOverGroundTrue" => get_f64_value(&self.course_over_ground_true),
             "course" => get_path(path, &(self.course.as_ref())),
             "courseRhumbline" => get_path(path, &(self.course_rhumbline.as_ref())),
             "courseGreatCircle" => get_path(path, &(self.course_great_circle.as_ref())),
-            "closestApproach" => Err(SignalKGetError::TBD),
-            "racing" => Err(SignalKGetError::TBD),
+            "closestApproach" => get_path(path, &(self.closest_approach.as_ref())),
+            "racing" => get_path(path, &(self.racing.as_ref())),
             "magneticVariation" => get_f64_value(&self.magnetic_variation),
             "magneticVariationAgeOfService" => {
                 get_f64_value(&self.magnetic_variation_age_of_service)
             }
-            "destination" => Err(SignalKGetError::TBD),
+            "destination" => Err(SignalKGetError::WrongDataType),
             "gnss" => get_path(path, &(self.gnss.as_ref())),
             "headingMagnetic" => get_f64_value(&self.heading_magnetic),
             "magneticDeviation" => get_f64_value(&self.magnetic_deviation),
             "headingCompass" => get_f64_value(&self.heading_compass),
             "headingTrue" => get_f64_value(&self.heading_true),
             "position" => get_path(path, &(self.position.as_ref())),
-            "attitude" => Err(SignalKGetError::TBD),
-            "maneuver" => Err(SignalKGetError::TBD),
+            "attitude" => get_path(path, &(self.attitude.as_ref())),
+            "maneuver" => Err(SignalKGetError::WrongDataType),
             "rateOfTurn" => get_f64_value(&self.rate_of_turn),
             "speedOverGround" => get_f64_value(&self.speed_over_ground),
             "speedThroughWater" => get_f64_value(&self.speed_through_water),
@@ -76,11 +86,11 @@ impl Path<f64> for V1Navigation {
             "speedThroughWaterLongitudinal" => {
                 get_f64_value(&self.speed_through_water_longitudinal)
             }
-            "leewayAngle" => get_f64_value(&self.speed_through_water_longitudinal),
-            "log" => get_f64_value(&self.speed_through_water_longitudinal),
-            "trip" => Err(SignalKGetError::TBD), // get_path(path, &(self.trip.as_ref())),
-            "state" => Err(SignalKGetError::TBD),
-            "anchor" => Err(SignalKGetError::TBD),
+            "leewayAngle" => get_f64_value(&self.leeway_angle),
+            "log" => get_f64_value(&self.log),
+            "trip" => get_f64_value_for_path(&mut path.to_vec(), &self.trip),
+            "state" => Err(SignalKGetError::WrongDataType),
+            "anchor" => get_path(path, &(self.anchor.as_ref())),
             "datetime" => Err(SignalKGetError::WrongDataType),
 
             &_ => Err(SignalKGetError::NoSuchPath),
@@ -93,23 +103,32 @@ impl V1Navigation {
         V1NavigationBuilder::default()
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    /// The distance in meters from the anchor to the position of the vessel,
+    /// when both are known
+    pub fn anchor_distance(&self) -> Option<f64> {
+        let position = self.position.as_ref()?.value.as_ref()?;
+        self.anchor.as_ref()?.distance_to(position)
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
+            "lights" => update_value(&mut self.lights, value, info),
             "courseOverGroundMagnetic" => {
-                self.course_over_ground_magnetic =
-                    Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "courseOverGroundTrue" => {
-                self.course_over_ground_true =
-                    Some(V1NumberValue::builder().json_value(value).build())
+                update_value(&mut self.course_over_ground_magnetic, value, info)
             }
+            "courseOverGroundTrue" => update_value(&mut self.course_over_ground_true, value, info),
             "course" => {
                 if self.course.is_none() {
                     self.course = Some(V1CourseApi::default());
                 }
                 if let Some(ref mut course) = self.course {
                     path.remove(0);
-                    course.update(path, value);
+                    course.update(path, value, info);
                 }
             }
             "courseRhumbline" => {
@@ -118,7 +137,7 @@ impl V1Navigation {
                 }
                 if let Some(ref mut course) = self.course_rhumbline {
                     path.remove(0);
-                    course.update(path, value);
+                    course.update(path, value, info);
                 }
             }
             "courseGreatCircle" => {
@@ -127,15 +146,31 @@ impl V1Navigation {
                 }
                 if let Some(ref mut course) = self.course_great_circle {
                     path.remove(0);
-                    course.update(path, value);
+                    course.update(path, value, info);
                 }
             }
-            "magneticVariation" => {
-                self.magnetic_variation = Some(V1NumberValue::builder().json_value(value).build())
+            "closestApproach" => update_value(&mut self.closest_approach, value, info),
+            "racing" => {
+                if self.racing.is_none() {
+                    self.racing = Some(V1Racing::default());
+                }
+                if let Some(ref mut racing) = self.racing {
+                    path.remove(0);
+                    racing.update(path, value, info);
+                }
             }
+            "magneticVariation" => update_value(&mut self.magnetic_variation, value, info),
             "magneticVariationAgeOfService" => {
-                self.magnetic_variation_age_of_service =
-                    Some(V1NumberValue::builder().json_value(value).build())
+                update_value(&mut self.magnetic_variation_age_of_service, value, info)
+            }
+            "destination" => {
+                if self.destination.is_none() {
+                    self.destination = Some(V1Destination::default());
+                }
+                if let Some(ref mut destination) = self.destination {
+                    path.remove(0);
+                    destination.update(path, value, info);
+                }
             }
             "gnss" => {
                 if self.gnss.is_none() {
@@ -143,32 +178,20 @@ impl V1Navigation {
                 }
                 if let Some(ref mut gnss) = self.gnss {
                     path.remove(0);
-                    gnss.update(path, value);
+                    gnss.update(path, value, info);
                 }
             }
 
-            "headingMagnetic" => {
-                self.heading_magnetic = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "magneticDeviation" => {
-                self.magnetic_deviation = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "headingCompass" => {
-                self.heading_compass = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "headingTrue" => {
-                self.heading_true = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "position" => self.position = Some(V1PositionType::builder().json_value(value).build()),
-            "rateOfTurn" => {
-                self.rate_of_turn = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "speedOverGround" => {
-                self.speed_over_ground = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "speedThroughWater" => {
-                self.speed_through_water = Some(V1NumberValue::builder().json_value(value).build())
-            }
+            "headingMagnetic" => update_value(&mut self.heading_magnetic, value, info),
+            "magneticDeviation" => update_value(&mut self.magnetic_deviation, value, info),
+            "headingCompass" => update_value(&mut self.heading_compass, value, info),
+            "headingTrue" => update_value(&mut self.heading_true, value, info),
+            "position" => update_value(&mut self.position, value, info),
+            "attitude" => update_value(&mut self.attitude, value, info),
+            "maneuver" => update_value(&mut self.maneuver, value, info),
+            "rateOfTurn" => update_value(&mut self.rate_of_turn, value, info),
+            "speedOverGround" => update_value(&mut self.speed_over_ground, value, info),
+            "speedThroughWater" => update_value(&mut self.speed_through_water, value, info),
             "speedThroughWaterReferenceType" => {
                 if let Some(s) = value.as_str() {
                     self.speed_through_water_reference_type =
@@ -176,26 +199,33 @@ impl V1Navigation {
                 }
             }
             "speedThroughWaterTransverse" => {
-                self.speed_through_water_transverse =
-                    Some(V1NumberValue::builder().json_value(value).build())
+                update_value(&mut self.speed_through_water_transverse, value, info)
             }
             "speedThroughWaterLongitudinal" => {
-                self.speed_through_water_longitudinal =
-                    Some(V1NumberValue::builder().json_value(value).build())
+                update_value(&mut self.speed_through_water_longitudinal, value, info)
             }
-            "leewayAngle" => {
-                self.leeway_angle = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "log" => self.log = Some(V1NumberValue::builder().json_value(value).build()),
+            "leewayAngle" => update_value(&mut self.leeway_angle, value, info),
+            "log" => update_value(&mut self.log, value, info),
             "trip" => {
                 if self.trip.is_none() {
                     self.trip = Some(V1Trip::default());
                 }
                 if let Some(ref mut trip) = self.trip {
                     path.remove(0);
-                    trip.update(path, value);
+                    trip.update(path, value, info);
+                }
+            }
+            "state" => update_value(&mut self.state, value, info),
+            "anchor" => {
+                if self.anchor.is_none() {
+                    self.anchor = Some(V1Anchor::default());
+                }
+                if let Some(ref mut anchor) = self.anchor {
+                    path.remove(0);
+                    anchor.update(path, value, info);
                 }
             }
+            "datetime" if info.keeps_value() => {}
             "datetime" => {
                 let datetime: Result<V1DateTime, serde_json::Error> =
                     serde_json::from_value(value.clone());
@@ -219,25 +249,25 @@ impl V1Navigation {
 
 #[derive(Default)]
 pub struct V1NavigationBuilder {
-    // pub lights: Option<V1Lights>,
+    lights: Option<V1NavigationLights>,
     course_over_ground_magnetic: Option<V1NumberValue>,
     course_over_ground_true: Option<V1NumberValue>,
     course: Option<V1CourseApi>,
     course_rhumbline: Option<V1Course>,
     course_great_circle: Option<V1Course>,
-    // pub closest_approach: Option<V1ClosestApproach>,
-    // pub racing: Option<V1Racing>,
+    closest_approach: Option<V1ClosestApproach>,
+    racing: Option<V1Racing>,
     magnetic_variation: Option<V1NumberValue>,
     magnetic_variation_age_of_service: Option<V1NumberValue>,
-    // pub destination: Option<V1Destination>,
+    destination: Option<V1Destination>,
     gnss: Option<V1gnss>,
     heading_magnetic: Option<V1NumberValue>,
     magnetic_deviation: Option<V1NumberValue>,
     heading_compass: Option<V1NumberValue>,
     heading_true: Option<V1NumberValue>,
     position: Option<V1PositionType>,
-    // pub attitude: Option<V1AttitudeType>,
-    // pub maneuver: Option<V1Maneuver>,
+    attitude: Option<V1AttitudeType>,
+    maneuver: Option<V1Maneuver>,
     rate_of_turn: Option<V1NumberValue>,
     speed_over_ground: Option<V1NumberValue>,
     speed_through_water: Option<V1NumberValue>,
@@ -247,12 +277,16 @@ pub struct V1NavigationBuilder {
     leeway_angle: Option<V1NumberValue>,
     log: Option<V1NumberValue>,
     trip: Option<V1Trip>,
-    // pub state: Option<V1State>,
-    // pub anchor: Option<V1Anchor>,
+    state: Option<V1NavigationState>,
+    anchor: Option<V1Anchor>,
     datetime: Option<V1DateTime>,
 }
 
 impl V1NavigationBuilder {
+    pub fn lights(mut self, value: V1NavigationLights) -> V1NavigationBuilder {
+        self.lights = Some(value);
+        self
+    }
     pub fn course_over_ground_magnetic(mut self, value: V1NumberValue) -> V1NavigationBuilder {
         self.course_over_ground_magnetic = Some(value);
         self
@@ -269,6 +303,14 @@ impl V1NavigationBuilder {
         self.course_great_circle = Some(value);
         self
     }
+    pub fn closest_approach(mut self, value: V1ClosestApproach) -> V1NavigationBuilder {
+        self.closest_approach = Some(value);
+        self
+    }
+    pub fn racing(mut self, value: V1Racing) -> V1NavigationBuilder {
+        self.racing = Some(value);
+        self
+    }
     pub fn magnetic_variation(mut self, value: V1NumberValue) -> V1NavigationBuilder {
         self.magnetic_variation = Some(value);
         self
@@ -280,6 +322,10 @@ impl V1NavigationBuilder {
         self.magnetic_variation_age_of_service = Some(value);
         self
     }
+    pub fn destination(mut self, value: V1Destination) -> V1NavigationBuilder {
+        self.destination = Some(value);
+        self
+    }
     pub fn heading_magnetic(mut self, value: V1NumberValue) -> V1NavigationBuilder {
         self.heading_magnetic = Some(value);
         self
@@ -300,6 +346,14 @@ impl V1NavigationBuilder {
         self.position = Some(position);
         self
     }
+    pub fn attitude(mut self, value: V1AttitudeType) -> V1NavigationBuilder {
+        self.attitude = Some(value);
+        self
+    }
+    pub fn maneuver(mut self, value: V1Maneuver) -> V1NavigationBuilder {
+        self.maneuver = Some(value);
+        self
+    }
     pub fn rate_of_turn(mut self, value: V1NumberValue) -> V1NavigationBuilder {
         self.rate_of_turn = Some(value);
         self
@@ -332,12 +386,21 @@ impl V1NavigationBuilder {
         self.trip = Some(value);
         self
     }
+    pub fn state(mut self, value: V1NavigationState) -> V1NavigationBuilder {
+        self.state = Some(value);
+        self
+    }
+    pub fn anchor(mut self, value: V1Anchor) -> V1NavigationBuilder {
+        self.anchor = Some(value);
+        self
+    }
     pub fn datetime(mut self, value: V1DateTime) -> V1NavigationBuilder {
         self.datetime = Some(value);
         self
     }
     pub fn build(self) -> V1Navigation {
         V1Navigation {
+            lights: self.lights,
             course_over_ground_magnetic: self.course_over_ground_magnetic,
             speed_over_ground: self.speed_over_ground,
             speed_through_water: self.speed_through_water,
@@ -349,17 +412,24 @@ impl V1NavigationBuilder {
             course_over_ground_true: self.course_over_ground_true,
             course_rhumbline: self.course_rhumbline,
             course_great_circle: self.course_great_circle,
+            closest_approach: self.closest_approach,
+            racing: self.racing,
             magnetic_variation: self.magnetic_variation,
             magnetic_variation_age_of_service: self.magnetic_variation_age_of_service,
+            destination: self.destination,
             gnss: self.gnss,
             heading_magnetic: self.heading_magnetic,
             magnetic_deviation: self.magnetic_deviation,
             heading_compass: self.heading_compass,
             heading_true: self.heading_true,
             position: self.position,
+            attitude: self.attitude,
+            maneuver: self.maneuver,
             rate_of_turn: self.rate_of_turn,
             log: self.log,
             trip: self.trip,
+            state: self.state,
+            anchor: self.anchor,
             datetime: self.datetime,
         }
     }
@@ -374,6 +444,9 @@ pub struct V1Trip {
 
 impl F64CompatiblePath for V1Trip {
     fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
         match path[0] {
             "log" => get_f64_value(&self.log),
             "lastReset" => Err(SignalKGetError::WrongDataType),
@@ -386,9 +459,15 @@ impl V1Trip {
     pub fn builder() -> V1TripBuilder {
         V1TripBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
-            "log" => self.log = Some(V1NumberValue::builder().json_value(value).build()),
+            "lastReset" if info.keeps_value() => {}
+            "log" => update_value(&mut self.log, value, info),
             "lastReset" => {
                 let val: Result<V1DateTime, serde_json::Error> =
                     serde_json::from_value(value.clone());
@@ -433,8 +512,48 @@ pub struct V1PositionType {
     pub timestamp: Option<String>,
     #[serde(rename = "$source")]
     pub source: Option<String>,
+    #[serde(rename = "source")]
+    pub def_source: Option<Box<V1DefSource>>,
     pub pgn: Option<f64>,
     pub sentence: Option<String>,
+    pub meta: Option<Box<V1Meta>>,
+    pub values: Option<HashMap<String, V1SourceValue<V1PositionValue>>>,
+}
+
+impl UpdatableValue for V1PositionType {
+    fn set_value(&mut self, value: &Value) {
+        self.value = V1PositionType::builder().json_value(value).build().value;
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if let Some(ref timestamp) = info.timestamp {
+            self.timestamp = Some(timestamp.clone());
+        }
+        if let Some(ref source) = info.ref_source {
+            self.source = Some(source.clone());
+        }
+        if let Some(ref def_source) = info.source {
+            self.pgn = def_source.pgn.map(f64::from);
+            self.sentence = def_source.sentence.clone();
+            self.def_source = Some(Box::new(def_source.clone()));
+        }
+        if let Some(ref meta) = info.meta {
+            self.meta = Some(Box::new(meta.clone()));
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        add_source_value(
+            &mut self.values,
+            self.source.as_deref().unwrap_or_default(),
+            || V1SourceValue {
+                value: self.value.clone(),
+                timestamp: self.timestamp.clone(),
+                pgn: self.pgn,
+                sentence: self.sentence.clone(),
+            },
+            value,
+            info,
+        );
+    }
 }
 
 impl Path<f64> for V1PositionType {
@@ -481,8 +600,7 @@ impl F64CompatiblePath for V1PositionType {
             Err(SignalKGetError::NoSuchPath)
         } else {
             match path[0] {
-                "longitude" => Err(SignalKGetError::TBD),
-                "latitude" => Err(SignalKGetError::TBD),
+                "longitude" | "latitude" | "altitude" => self.get_path(path),
                 &_ => Err(SignalKGetError::NoSuchPath),
             }
         }
@@ -495,15 +613,14 @@ impl V1PositionType {
     }
 
     pub fn from_value(value: &serde_json::Value) -> Option<V1PositionType> {
-        let type_result: Result<V1PositionType, serde_json::Error> =
-            serde_json::from_value(value.clone());
-        if let Ok(position) = type_result {
-            Some(position)
-        } else {
-            None
-        }
-    }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+        serde_json::from_value(value.clone()).ok()
+    }
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        _info: &V1UpdateInfo,
+    ) {
         if path.is_empty() {
             log::warn!("V1PositionType: Empty path: {:?}::{:?}", path, value);
         } else {
@@ -525,8 +642,11 @@ pub struct V1PositionTypeBuilder {
     pub value: Option<V1PositionValue>,
     pub timestamp: Option<String>,
     pub source: Option<String>,
+    pub def_source: Option<Box<V1DefSource>>,
     pub pgn: Option<f64>,
     pub sentence: Option<String>,
+    pub meta: Option<Box<V1Meta>>,
+    pub values: Option<HashMap<String, V1SourceValue<V1PositionValue>>>,
 }
 
 impl V1PositionTypeBuilder {
@@ -584,6 +704,10 @@ impl V1PositionTypeBuilder {
         self.source = Some(source);
         self
     }
+    pub fn def_source(mut self, def_source: V1DefSource) -> V1PositionTypeBuilder {
+        self.def_source = Some(Box::new(def_source));
+        self
+    }
     pub fn pgn(mut self, pgn: f64) -> V1PositionTypeBuilder {
         self.pgn = Some(pgn);
         self
@@ -592,13 +716,30 @@ impl V1PositionTypeBuilder {
         self.sentence = Some(sentence);
         self
     }
+    pub fn meta(mut self, meta: V1Meta) -> V1PositionTypeBuilder {
+        self.meta = Some(Box::new(meta));
+        self
+    }
+    pub fn add_source_value(
+        mut self,
+        source: String,
+        value: V1SourceValue<V1PositionValue>,
+    ) -> V1PositionTypeBuilder {
+        self.values
+            .get_or_insert_with(HashMap::new)
+            .insert(source, value);
+        self
+    }
     pub fn build(self) -> V1PositionType {
         V1PositionType {
             value: self.value,
             timestamp: self.timestamp,
             source: self.source,
+            def_source: self.def_source,
             pgn: self.pgn,
             sentence: self.sentence,
+            meta: self.meta,
+            values: self.values,
         }
     }
 }
@@ -643,16 +784,534 @@ impl V1PositionValue {
             altitude: Some(altitude),
         }
     }
+
+    /// The great circle distance in meters to another position
+    pub fn distance_to(&self, other: &V1PositionValue) -> f64 {
+        const EARTH_RADIUS: f64 = 6_371_000.0;
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
+/// Vessel attitude: roll, pitch and yaw
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1AttitudeType {
+    pub value: Option<V1AttitudeValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1AttitudeValue>>>,
+}
+
+impl V1AttitudeType {
+    pub fn builder() -> V1AttitudeTypeBuilder {
+        V1AttitudeTypeBuilder::default()
+    }
+}
+
+impl UpdatableValue for V1AttitudeType {
+    fn set_value(&mut self, value: &Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+impl Path<f64> for V1AttitudeType {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1AttitudeType::get_path({:?})", path);
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        if let Some(ref attitude) = self.value {
+            attitude.get_path(path)
+        } else {
+            Err(SignalKGetError::ValueNotSet)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1AttitudeTypeBuilder {
+    value: Option<V1AttitudeValue>,
+    common_value_fields: Option<V1CommonValueFields>,
+    values: Option<HashMap<String, V1SourceValue<V1AttitudeValue>>>,
+}
+
+impl V1AttitudeTypeBuilder {
+    pub fn value(mut self, value: V1AttitudeValue) -> V1AttitudeTypeBuilder {
+        self.value = Some(value);
+        self
+    }
+    pub fn common_value_fields(mut self, value: V1CommonValueFields) -> V1AttitudeTypeBuilder {
+        self.common_value_fields = Some(value);
+        self
+    }
+    pub fn add_source_value(
+        mut self,
+        source: String,
+        value: V1SourceValue<V1AttitudeValue>,
+    ) -> V1AttitudeTypeBuilder {
+        self.values
+            .get_or_insert_with(HashMap::new)
+            .insert(source, value);
+        self
+    }
+    pub fn build(self) -> V1AttitudeType {
+        V1AttitudeType {
+            value: self.value,
+            common_value_fields: self.common_value_fields,
+            values: self.values,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct V1AttitudeValue {
+    /// Vessel roll, +ve is list to starboard
+    pub roll: Option<f64>,
+    /// Pitch, +ve is bow up
+    pub pitch: Option<f64>,
+    /// Yaw, +ve is heading change to starboard
+    pub yaw: Option<f64>,
+}
+
+impl Path<f64> for V1AttitudeValue {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        let value = match path[0] {
+            "roll" => self.roll,
+            "pitch" => self.pitch,
+            "yaw" => self.yaw,
+            &_ => return Err(SignalKGetError::NoSuchPath),
+        };
+        value.ok_or(SignalKGetError::ValueNotSet)
+    }
+}
+
+impl V1AttitudeValue {
+    pub fn new(roll: f64, pitch: f64, yaw: f64) -> V1AttitudeValue {
+        V1AttitudeValue {
+            roll: Some(roll),
+            pitch: Some(pitch),
+            yaw: Some(yaw),
+        }
+    }
+}
+
+/// Special maneuver such as regional passing arrangement, as reported by AIS
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Maneuver {
+    pub value: Option<V1ManeuverValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1ManeuverValue>>>,
+}
+
+impl V1Maneuver {
+    pub fn new(value: V1ManeuverValue) -> V1Maneuver {
+        V1Maneuver {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl UpdatableValue for V1Maneuver {
+    fn set_value(&mut self, value: &Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum V1ManeuverValue {
+    #[serde(rename = "Not available")]
+    NotAvailable,
+    #[serde(rename = "No special maneuver")]
+    NoSpecialManeuver,
+    #[serde(rename = "Special maneuver")]
+    SpecialManeuver,
+}
+
+/// Current navigational state of the vessel
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1NavigationState {
+    pub value: Option<V1NavigationStateValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1NavigationStateValue>>>,
+}
+
+impl V1NavigationState {
+    pub fn new(value: V1NavigationStateValue) -> V1NavigationState {
+        V1NavigationState {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl UpdatableValue for V1NavigationState {
+    fn set_value(&mut self, value: &Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum V1NavigationStateValue {
+    #[serde(rename = "not under command")]
+    NotUnderCommand,
+    #[serde(rename = "anchored")]
+    Anchored,
+    #[serde(rename = "moored")]
+    Moored,
+    #[serde(rename = "sailing")]
+    Sailing,
+    #[serde(rename = "motoring")]
+    Motoring,
+    #[serde(rename = "towing < 200m")]
+    TowingShort,
+    #[serde(rename = "towing > 200m")]
+    TowingLong,
+    #[serde(rename = "pushing")]
+    Pushing,
+    #[serde(rename = "fishing")]
+    Fishing,
+    #[serde(rename = "fishing-hampered")]
+    FishingHampered,
+    #[serde(rename = "trawling")]
+    Trawling,
+    #[serde(rename = "trawling-shooting")]
+    TrawlingShooting,
+    #[serde(rename = "trawling-hauling")]
+    TrawlingHauling,
+    #[serde(rename = "pilotage")]
+    Pilotage,
+    #[serde(rename = "not-under-way")]
+    NotUnderWay,
+    #[serde(rename = "aground")]
+    Aground,
+    #[serde(rename = "restricted manouverability")]
+    RestrictedManouverability,
+    #[serde(rename = "restricted manouverability towing < 200m")]
+    RestrictedManouverabilityTowingShort,
+    #[serde(rename = "restricted manouverability towing > 200m")]
+    RestrictedManouverabilityTowingLong,
+    #[serde(rename = "restricted manouverability underwater operations")]
+    RestrictedManouverabilityUnderwaterOperations,
+    #[serde(rename = "constrained by draft")]
+    ConstrainedByDraft,
+    #[serde(rename = "mine clearance operations")]
+    MineClearanceOperations,
+}
+
+/// Current state of the vessels navigation lights
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1NavigationLights {
+    pub value: Option<V1NavigationLightsValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1NavigationLightsValue>>>,
+}
+
+impl V1NavigationLights {
+    pub fn new(value: V1NavigationLightsValue) -> V1NavigationLights {
+        V1NavigationLights {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl UpdatableValue for V1NavigationLights {
+    fn set_value(&mut self, value: &Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum V1NavigationLightsValue {
+    #[serde(rename = "off")]
+    Off,
+    #[serde(rename = "fault")]
+    Fault,
+    #[serde(rename = "anchored")]
+    Anchored,
+    #[serde(rename = "sailing")]
+    Sailing,
+    #[serde(rename = "motoring")]
+    Motoring,
+    #[serde(rename = "towing < 200m")]
+    TowingShort,
+    #[serde(rename = "towing > 200m")]
+    TowingLong,
+    #[serde(rename = "pushing")]
+    Pushing,
+    #[serde(rename = "fishing")]
+    Fishing,
+    #[serde(rename = "fishing-hampered")]
+    FishingHampered,
+    #[serde(rename = "trawling")]
+    Trawling,
+    #[serde(rename = "trawling-shooting")]
+    TrawlingShooting,
+    #[serde(rename = "trawling-hauling")]
+    TrawlingHauling,
+    #[serde(rename = "pilotage")]
+    Pilotage,
+    #[serde(rename = "not-under-way")]
+    NotUnderWay,
+    #[serde(rename = "aground")]
+    Aground,
+    #[serde(rename = "restricted manouverability")]
+    RestrictedManouverability,
+    #[serde(rename = "restricted manouverability towing < 200m")]
+    RestrictedManouverabilityTowingShort,
+    #[serde(rename = "restricted manouverability towing > 200m")]
+    RestrictedManouverabilityTowingLong,
+    #[serde(rename = "restricted manouverability underwater operations")]
+    RestrictedManouverabilityUnderwaterOperations,
+    #[serde(rename = "constrained by draft")]
+    ConstrainedByDraft,
+    #[serde(rename = "mine clearance")]
+    MineClearance,
+}
+
+/// The intended destination of this trip
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Destination {
+    /// Common name of the destination, e.g. 'Fiji', also used in AIS messages
+    pub common_name: Option<V1StringValue>,
+    /// Expected time of arrival at destination waypoint
+    pub eta: Option<V1StringValue>,
+    /// UUID of destination waypoint
+    pub waypoint: Option<V1StringValue>,
+}
+
+impl V1Destination {
+    pub fn builder() -> V1DestinationBuilder {
+        V1DestinationBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "commonName" => update_value(&mut self.common_name, value, info),
+            "eta" => update_value(&mut self.eta, value, info),
+            "waypoint" => update_value(&mut self.waypoint, value, info),
+            &_ => {
+                log::warn!(
+                    "V1Destination: Unknown value to update: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1DestinationBuilder {
+    common_name: Option<V1StringValue>,
+    eta: Option<V1StringValue>,
+    waypoint: Option<V1StringValue>,
+}
+
+impl V1DestinationBuilder {
+    pub fn common_name(mut self, value: V1StringValue) -> V1DestinationBuilder {
+        self.common_name = Some(value);
+        self
+    }
+    pub fn eta(mut self, value: V1StringValue) -> V1DestinationBuilder {
+        self.eta = Some(value);
+        self
+    }
+    pub fn waypoint(mut self, value: V1StringValue) -> V1DestinationBuilder {
+        self.waypoint = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Destination {
+        V1Destination {
+            common_name: self.common_name,
+            eta: self.eta,
+            waypoint: self.waypoint,
+        }
+    }
+}
+
+/// Calculated closest point of approach to this vessel, e.g. from AIS, kept
+/// in the navigation of the other vessel
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1ClosestApproach {
+    pub value: Option<V1ClosestApproachValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1ClosestApproachValue>>>,
+}
+
+impl V1ClosestApproach {
+    pub fn new(value: V1ClosestApproachValue) -> V1ClosestApproach {
+        V1ClosestApproach {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl UpdatableValue for V1ClosestApproach {
+    fn set_value(&mut self, value: &Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+impl Path<f64> for V1ClosestApproach {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1ClosestApproach::get_path({:?})", path);
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        let Some(ref closest_approach) = self.value else {
+            return Err(SignalKGetError::ValueNotSet);
+        };
+        let value = match path[0] {
+            "distance" => closest_approach.distance,
+            "timeTo" => closest_approach.time_to,
+            &_ => return Err(SignalKGetError::NoSuchPath),
+        };
+        value.ok_or(SignalKGetError::ValueNotSet)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1ClosestApproachValue {
+    /// Closest Point of Approach (CPA), distance between own vessel and other vessel
+    pub distance: Option<f64>,
+    /// Time to Closest Point of Approach (TCPA), negative when it has passed
+    pub time_to: Option<f64>,
+}
+
+impl V1ClosestApproachValue {
+    pub fn new(distance: f64, time_to: f64) -> V1ClosestApproachValue {
+        V1ClosestApproachValue {
+            distance: Some(distance),
+            time_to: Some(time_to),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::definitions::F64Compatible;
-    use crate::helper_functions::get_path;
-    use crate::navigation::V1Navigation;
+    use crate::helper_functions::{get_f64_value_for_path, get_path};
+    use crate::navigation::{
+        V1AttitudeValue, V1ClosestApproach, V1ClosestApproachValue, V1Maneuver, V1ManeuverValue,
+        V1Navigation, V1NavigationLightsValue, V1NavigationState, V1NavigationStateValue,
+        V1PositionType, V1PositionValue,
+    };
     use crate::navigation_course::V1Course;
-    use crate::SignalKGetError;
-    use serde_json::{Number, Value};
+    use crate::{SignalKGetError, V1FullFormat, V1UpdateInfo, V1Vessel};
+    use serde_json::{json, Number, Value};
     use std::fs::File;
     use std::io::BufReader;
     use std::path::Path;
@@ -669,7 +1328,7 @@ mod tests {
             .build();
         let mut path = vec!["courseRhumbline", "crossTrackError"];
         let value = Value::Number(Number::from_f64(1.5).unwrap());
-        navigation.update(&mut path, &value);
+        navigation.update(&mut path, &value, &V1UpdateInfo::default());
         assert_eq!(
             navigation
                 .course_rhumbline
@@ -691,7 +1350,7 @@ mod tests {
             .build();
         let mut path = vec!["courseRhumbline", "bearingTrackTrue"];
         let value = Value::Number(Number::from_f64(0.1234).unwrap());
-        navigation.update(&mut path, &value);
+        navigation.update(&mut path, &value, &V1UpdateInfo::default());
         assert_eq!(
             navigation
                 .course_rhumbline
@@ -712,7 +1371,7 @@ mod tests {
             .build();
         let mut path = vec!["courseRhumbline", "bearingTrackMagnetic"];
         let value = Value::Number(Number::from_f64(1.2345).unwrap());
-        navigation.update(&mut path, &value);
+        navigation.update(&mut path, &value, &V1UpdateInfo::default());
         assert_eq!(
             navigation
                 .course_rhumbline
@@ -808,6 +1467,62 @@ mod tests {
         )
     }
     #[test]
+    fn get_gnss_satellite_elevation_from_navigation() {
+        assert_eq!(
+            get_path_from_navigation_file(
+                ".gnss.satellitesInView.satellites.1.elevation",
+                "tests/test_data/navigation.json"
+            ),
+            Ok(1.1694)
+        )
+    }
+    #[test]
+    fn get_leeway_angle_and_logs_from_navigation() {
+        let leaf = |value: f64| json!({"value": value, "timestamp": "", "$source": "self"});
+        let navigation: V1Navigation = serde_json::from_value(json!({
+            "speedThroughWaterLongitudinal": leaf(1.0),
+            "leewayAngle": leaf(0.05),
+            "log": leaf(12345.0),
+            "trip": {"log": leaf(678.0)}
+        }))
+        .unwrap();
+        assert_eq!(get_path(&["", "leewayAngle"], &Some(&navigation)), Ok(0.05));
+        assert_eq!(get_path(&["", "log"], &Some(&navigation)), Ok(12345.0));
+        assert_eq!(
+            get_path(&["", "trip", "log"], &Some(&navigation)),
+            Ok(678.0)
+        );
+    }
+    #[test]
+    fn get_log_from_navigation() {
+        assert_eq!(
+            get_path_from_navigation_file(".log", "tests/test_data/navigation.json"),
+            Ok(17441395.0)
+        )
+    }
+    #[test]
+    fn get_position_as_f64_compatible_path() {
+        let position = Some(
+            V1PositionType::builder()
+                .value(V1PositionValue::new_2d(59.0, 18.0))
+                .build(),
+        );
+        let mut path = vec!["position", "latitude"];
+        assert_eq!(get_f64_value_for_path(&mut path, &position), Ok(59.0));
+        let mut path = vec!["position", "altitude"];
+        assert_eq!(
+            get_f64_value_for_path(&mut path, &position),
+            Err(SignalKGetError::ValueNotSet)
+        );
+    }
+    #[test]
+    fn get_trip_log_from_navigation() {
+        assert_eq!(
+            get_path_from_navigation_file(".trip.log", "tests/test_data/navigation.json"),
+            Ok(80247.0)
+        )
+    }
+    #[test]
     fn get_speed_through_water_from_navigation() {
         assert_eq!(
             get_path_from_navigation_file(".speedThroughWater", "tests/test_data/navigation.json"),
@@ -1039,6 +1754,185 @@ mod tests {
         )
     }
 
+    #[test]
+    fn update_navigation_attitude() {
+        let mut navigation = V1Navigation::default();
+        let info = V1UpdateInfo {
+            ref_source: Some("n2k1.204".into()),
+            timestamp: Some("2013-10-08T15:47:28.263Z".into()),
+            ..Default::default()
+        };
+        let value = json!({"roll": -0.02, "pitch": 0.01, "yaw": 0.3});
+        navigation.update(&mut vec!["attitude"], &value, &info);
+        assert_eq!(
+            navigation.attitude.as_ref().unwrap().value,
+            Some(V1AttitudeValue::new(-0.02, 0.01, 0.3))
+        );
+        let path = vec!["", "attitude", "roll"];
+        assert_eq!(get_path(&path, &Some(&navigation)), Ok(-0.02));
+        let path = vec!["", "attitude"];
+        assert_eq!(
+            get_path(&path, &Some(&navigation)),
+            Err(SignalKGetError::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn update_navigation_attitude_from_second_source() {
+        let mut navigation = V1Navigation::default();
+        let imu = V1UpdateInfo {
+            ref_source: Some("imu.1".into()),
+            ..Default::default()
+        };
+        let compass = V1UpdateInfo {
+            ref_source: Some("n2k1.204".into()),
+            source_value_only: true,
+            ..Default::default()
+        };
+        navigation.update(&mut vec!["attitude"], &json!({"roll": 0.1}), &imu);
+        navigation.update(&mut vec!["attitude"], &json!({"roll": 0.2}), &compass);
+        let attitude = navigation.attitude.unwrap();
+        assert_eq!(attitude.value.unwrap().roll, Some(0.1));
+        let values = attitude.values.unwrap();
+        assert_eq!(values["imu.1"].value.as_ref().unwrap().roll, Some(0.1));
+        assert_eq!(values["n2k1.204"].value.as_ref().unwrap().roll, Some(0.2));
+    }
+
+    #[test]
+    fn update_navigation_maneuver_and_state() {
+        let mut navigation = V1Navigation::default();
+        let info = V1UpdateInfo::default();
+        navigation.update(&mut vec!["state"], &json!("moored"), &info);
+        navigation.update(&mut vec!["maneuver"], &json!("Special maneuver"), &info);
+        assert_eq!(
+            navigation.state,
+            Some(V1NavigationState::new(V1NavigationStateValue::Moored))
+        );
+        assert_eq!(
+            navigation.maneuver,
+            Some(V1Maneuver::new(V1ManeuverValue::SpecialManeuver))
+        );
+        navigation.update(&mut vec!["state"], &json!("towing > 200m"), &info);
+        assert_eq!(
+            navigation.state.as_ref().unwrap().value,
+            Some(V1NavigationStateValue::TowingLong)
+        );
+        navigation.update(&mut vec!["state"], &Value::Null, &info);
+        assert_eq!(navigation.state, None);
+        let path = vec!["", "maneuver"];
+        assert_eq!(
+            get_path(&path, &Some(&navigation)),
+            Err(SignalKGetError::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn maneuver_from_sample() {
+        let file =
+            File::open("tests/specification/test_data/full-valid/maneuver-sample.json").unwrap();
+        let full: V1FullFormat = serde_json::from_reader(BufReader::new(file)).unwrap();
+        let navigation = full.get_self().unwrap().navigation.as_ref().unwrap();
+        let maneuver = navigation.maneuver.as_ref().unwrap();
+        assert_eq!(maneuver.value, Some(V1ManeuverValue::NoSpecialManeuver));
+        assert_eq!(
+            maneuver.common_value_fields.as_ref().unwrap().source,
+            "a.suitable.path"
+        );
+    }
+
+    #[test]
+    fn update_navigation_lights_and_destination() {
+        let mut navigation = V1Navigation::default();
+        let info = V1UpdateInfo {
+            ref_source: Some("n2k.43".into()),
+            timestamp: Some("2024-06-01T10:00:00Z".into()),
+            ..Default::default()
+        };
+        navigation.update(&mut vec!["lights"], &json!("motoring"), &info);
+        navigation.update(
+            &mut vec!["destination", "commonName"],
+            &json!("Mariehamn"),
+            &info,
+        );
+        navigation.update(
+            &mut vec!["destination", "eta"],
+            &json!("2024-06-01T18:30:00Z"),
+            &info,
+        );
+        assert_eq!(
+            navigation.lights.as_ref().unwrap().value,
+            Some(V1NavigationLightsValue::Motoring)
+        );
+        let full = V1FullFormat::builder()
+            .self_("vessels.urn:mrn:imo:mmsi:230099999".into())
+            .add_vessel(
+                "urn:mrn:imo:mmsi:230099999".into(),
+                V1Vessel::builder().navigation(navigation).build(),
+            )
+            .build();
+        assert_eq!(
+            full.get_for_path::<String>("self.navigation.destination.commonName".into()),
+            Ok("Mariehamn".to_string())
+        );
+        assert_eq!(
+            full.get_for_path::<String>("self.navigation.destination.eta".into()),
+            Ok("2024-06-01T18:30:00Z".to_string())
+        );
+        assert_eq!(
+            full.get_for_path::<V1NavigationLightsValue>("self.navigation.lights".into()),
+            Ok(V1NavigationLightsValue::Motoring)
+        );
+        assert_eq!(
+            full.get_f64_for_path("self.navigation.lights".into()),
+            Err(SignalKGetError::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn destination_from_sample() {
+        let file =
+            File::open("tests/specification/test_data/full-valid/nav-destination.json").unwrap();
+        let full: V1FullFormat = serde_json::from_reader(BufReader::new(file)).unwrap();
+        assert_eq!(
+            full.get_for_path::<String>("self.navigation.destination.commonName".into()),
+            Ok("Fiji".to_string())
+        );
+    }
+
+    #[test]
+    fn closest_approach_of_ais_target() {
+        let file =
+            File::open("tests/specification/test_data/full-valid/ais-full_cpa_tcpa.json").unwrap();
+        let full: V1FullFormat = serde_json::from_reader(BufReader::new(file)).unwrap();
+        let (context, _) = full
+            .query("vessels.*", "navigation.closestApproach")
+            .into_iter()
+            .map(|(context, path, _)| (context, path))
+            .next()
+            .unwrap();
+        assert_eq!(
+            full.get_f64_for_path(format!("{}.navigation.closestApproach.distance", context)),
+            Ok(250.0)
+        );
+        assert_eq!(
+            full.get_f64_for_path(format!("{}.navigation.closestApproach.timeTo", context)),
+            Ok(45.0)
+        );
+
+        let mut navigation = V1Navigation::default();
+        navigation.update(
+            &mut vec!["closestApproach"],
+            &json!({"distance": 31.2, "timeTo": -12.0}),
+            &V1UpdateInfo::default(),
+        );
+        assert_eq!(
+            navigation.closest_approach,
+            Some(V1ClosestApproach::new(V1ClosestApproachValue::new(
+                31.2, -12.0
+            )))
+        );
+    }
+
     fn get_path_from_navigation_file(
         path_string: &str,
         file_name: &str,
@@ -1048,7 +1942,6 @@ mod tests {
         let reader = BufReader::new(file);
         let course_with_point: V1Navigation = serde_json::from_reader(reader).unwrap();
         let path: Vec<&str> = path_string.split('.').collect();
-        let result = get_path(&path, &Some(&course_with_point));
-        result
+        get_path(&path, &Some(&course_with_point))
     }
 }
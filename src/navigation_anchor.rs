@@ -0,0 +1,201 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{update_value, V1Meta, V1NumberValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, get_path, Path};
+use crate::navigation::{V1PositionType, V1PositionValue};
+use crate::{SignalKGetError, V1NotificationValue};
+
+/// The anchor watch: where the anchor was dropped and how far the vessel
+/// may swing around it
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Anchor {
+    /// Radius of anchor alarm boundary. The distance from anchor to the center of the boat
+    pub max_radius: Option<V1NumberValue>,
+    /// Current distance to anchor
+    pub current_radius: Option<V1NumberValue>,
+    /// The actual anchor position of the vessel in 3 dimensions, probably an estimate at best
+    pub position: Option<V1PositionType>,
+    /// How the anchor alarm is raised, see `alarm`
+    pub meta: Option<V1Meta>,
+}
+
+impl Path<f64> for V1Anchor {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1Anchor::get_path({:?})", path);
+        match path[0] {
+            "maxRadius" => get_f64_value(&self.max_radius),
+            "currentRadius" => get_f64_value(&self.current_radius),
+            "position" => get_path(path, &(self.position.as_ref())),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1Anchor {
+    pub fn builder() -> V1AnchorBuilder {
+        V1AnchorBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            if info.meta_only {
+                self.meta = info.meta.clone();
+            }
+            return;
+        }
+        match path[0] {
+            "maxRadius" => update_value(&mut self.max_radius, value, info),
+            "currentRadius" => update_value(&mut self.current_radius, value, info),
+            "position" => update_value(&mut self.position, value, info),
+            &_ => {
+                log::warn!("V1Anchor: Unknown value to update: {:?}::{:?}", path, value);
+            }
+        }
+    }
+
+    /// The distance in meters from the anchor to `position`, when the anchor
+    /// position is known
+    pub fn distance_to(&self, position: &V1PositionValue) -> Option<f64> {
+        let anchor = self.position.as_ref()?.value.as_ref()?;
+        Some(anchor.distance_to(position))
+    }
+
+    /// Check if `position` is outside the anchor circle, `None` when the anchor
+    /// position or the max radius is not known
+    pub fn is_outside(&self, position: &V1PositionValue) -> Option<bool> {
+        let max_radius = self.max_radius.as_ref()?.value?;
+        Some(self.distance_to(position)? > max_radius)
+    }
+
+    /// The notification raised when the vessel is outside the anchor circle
+    ///
+    /// The methods are the `alarmMethod` of the anchor meta data, the message
+    /// is the one of its `alarm` zone when it has one.
+    pub fn alarm(&self, timestamp: Option<String>) -> V1NotificationValue {
+        let meta = self.meta.clone().unwrap_or_default();
+        let message = meta
+            .zones
+            .iter()
+            .flatten()
+            .find(|zone| zone.state == "alarm")
+            .and_then(|zone| zone.message.clone())
+            .unwrap_or_else(|| "The vessel is outside the anchor circle".to_string());
+        V1NotificationValue {
+            method: meta.methods_for("alarm"),
+            state: "alarm".to_string(),
+            message,
+            timestamp,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1AnchorBuilder {
+    max_radius: Option<V1NumberValue>,
+    current_radius: Option<V1NumberValue>,
+    position: Option<V1PositionType>,
+    meta: Option<V1Meta>,
+}
+
+impl V1AnchorBuilder {
+    pub fn max_radius(mut self, value: V1NumberValue) -> V1AnchorBuilder {
+        self.max_radius = Some(value);
+        self
+    }
+    pub fn current_radius(mut self, value: V1NumberValue) -> V1AnchorBuilder {
+        self.current_radius = Some(value);
+        self
+    }
+    pub fn position(mut self, value: V1PositionType) -> V1AnchorBuilder {
+        self.position = Some(value);
+        self
+    }
+    pub fn meta(mut self, value: V1Meta) -> V1AnchorBuilder {
+        self.meta = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Anchor {
+        V1Anchor {
+            max_radius: self.max_radius,
+            current_radius: self.current_radius,
+            position: self.position,
+            meta: self.meta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod anchor_tests {
+    use serde_json::json;
+
+    use crate::helper_functions::get_path;
+    use crate::navigation::V1Navigation;
+    use crate::navigation_anchor::V1Anchor;
+    use crate::{SignalKGetError, V1NumberValue, V1PositionType, V1PositionValue, V1UpdateInfo};
+
+    fn anchor() -> V1Anchor {
+        V1Anchor::builder()
+            .position(
+                V1PositionType::builder()
+                    .value(V1PositionValue::new_2d(59.0, 18.0))
+                    .build(),
+            )
+            .max_radius(V1NumberValue::builder().value(50.0).build())
+            .build()
+    }
+
+    #[test]
+    fn distance_to_anchor() {
+        let anchor = anchor();
+        let north = V1PositionValue::new_2d(59.0003, 18.0);
+        let distance = anchor.distance_to(&north).unwrap();
+        assert!((distance - 33.36).abs() < 0.1, "{}", distance);
+        assert_eq!(anchor.is_outside(&north), Some(false));
+        let east = V1PositionValue::new_2d(59.0, 18.001);
+        let distance = anchor.distance_to(&east).unwrap();
+        assert!((distance - 57.27).abs() < 0.1, "{}", distance);
+        assert_eq!(anchor.is_outside(&east), Some(true));
+        assert_eq!(V1Anchor::default().is_outside(&east), None);
+    }
+
+    #[test]
+    fn update_navigation_anchor() {
+        let mut navigation = V1Navigation::default();
+        let info = V1UpdateInfo::default();
+        navigation.update(
+            &mut vec!["anchor", "position"],
+            &json!({"latitude": 59.0, "longitude": 18.0}),
+            &info,
+        );
+        navigation.update(&mut vec!["anchor", "maxRadius"], &json!(50.0), &info);
+        assert_eq!(navigation.anchor, Some(anchor()));
+        let path = vec!["", "anchor", "maxRadius"];
+        assert_eq!(get_path(&path, &Some(&navigation)), Ok(50.0));
+        let path = vec!["", "anchor", "position", "latitude"];
+        assert_eq!(get_path(&path, &Some(&navigation)), Ok(59.0));
+        let path = vec!["", "anchor", "currentRadius"];
+        assert_eq!(
+            get_path(&path, &Some(&navigation)),
+            Err(SignalKGetError::ValueNotSet)
+        );
+    }
+
+    #[test]
+    fn repeated_keys_in_anchor() {
+        let navigation: V1Navigation = serde_json::from_str(
+            r#"{"anchor": {"currentRadius": {"value": 10, "timestamp": "", "$source": "self"},
+                "currentRadius": {"value": 50, "timestamp": "", "$source": "self"}}}"#,
+        )
+        .unwrap();
+        let anchor = navigation.anchor.unwrap();
+        assert_eq!(anchor.current_radius.unwrap().value, Some(50.0));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::definitions::{V1DateTime, V1StringValue, V2NumberValue};
+use crate::definitions::{update_value, V1DateTime, V1StringValue, V2NumberValue};
+use crate::delta::V1UpdateInfo;
 use crate::helper_functions::{get_f64_value, get_path, Path};
 use crate::{SignalKGetError, V1CommonValueFields, V1NumberValue, V1PositionType, V1PositionValue};
 use log::debug;
@@ -34,8 +35,11 @@ impl Path<f64> for V1CourseApi {
 }
 
 impl V1CourseApi {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &Value) {
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &Value, info: &V1UpdateInfo) {
         match path[0] {
+            "activeRoute" | "nextPoint" | "previousPoint" | "startTime" | "targetArrivalTime"
+            | "arrivalCircle"
+                if info.keeps_value() => {}
             "activeRoute" => self.active_route = V1CourseApiActiveRouteModel::from_value(value),
             "nextPoint" => self.next_point = V1CourseApiPointModel::from_value(value),
             "previousPoint" => self.previous_point = V1CourseApiPointModel::from_value(value),
@@ -48,7 +52,7 @@ impl V1CourseApi {
                 }
                 if let Some(ref mut calc_values) = self.calc_values {
                     path.remove(0);
-                    calc_values.update(path, value);
+                    calc_values.update(path, value, info);
                 }
             }
             &_ => {
@@ -91,13 +95,7 @@ impl V1CourseApiActiveRouteModel {
         if value.is_null() {
             None
         } else {
-            let route_result: Result<V1CourseApiActiveRouteModel, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(route_value) = route_result {
-                Some(route_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -128,13 +126,7 @@ impl V1CourseApiPointModel {
         if value.is_null() {
             None
         } else {
-            let route_result: Result<V1CourseApiPointModel, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(route_value) = route_result {
-                Some(route_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -182,17 +174,12 @@ impl V1CourseCalculationsModel {
         if value.is_null() {
             None
         } else {
-            let route_result: Result<V1CourseCalculationsModel, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(route_value) = route_result {
-                Some(route_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &Value) {
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &Value, info: &V1UpdateInfo) {
         match path[0] {
+            "calcMethod" | "estimatedTimeOfArrival" | "previousPoint" if info.keeps_value() => {}
             // "activeRoute" => self.calc_method = V1CourseCalculationsMethod::from_value(value),
             "calcMethod" => {
                 let val: Result<V1CourseCalculationsMethod, serde_json::Error> =
@@ -203,20 +190,18 @@ impl V1CourseCalculationsModel {
                     log::warn!("V1Trip: Invalid last reset value");
                 }
             }
-            "crossTrackError" => self.cross_track_error = V2NumberValue::from_value(value),
-            "bearingTrackTrue" => self.bearing_track_true = V2NumberValue::from_value(value),
-            "bearingTrackMagnetic" => {
-                self.bearing_track_magnetic = V2NumberValue::from_value(value)
-            }
+            "crossTrackError" => update_value(&mut self.cross_track_error, value, info),
+            "bearingTrackTrue" => update_value(&mut self.bearing_track_true, value, info),
+            "bearingTrackMagnetic" => update_value(&mut self.bearing_track_magnetic, value, info),
             "estimatedTimeOfArrival" => {
                 self.estimated_time_of_arrival = V1DateTime::from_value(value)
             }
-            "distance" => self.distance = V2NumberValue::from_value(value),
-            "bearingTrue" => self.bearing_true = V2NumberValue::from_value(value),
-            "bearingMagnetic" => self.bearing_magnetic = V2NumberValue::from_value(value),
-            "velocityMadeGood" => self.velocity_made_good = V2NumberValue::from_value(value),
-            "timeToGo" => self.time_to_go = V2NumberValue::from_value(value),
-            "targetSpeed" => self.target_speed = V2NumberValue::from_value(value),
+            "distance" => update_value(&mut self.distance, value, info),
+            "bearingTrue" => update_value(&mut self.bearing_true, value, info),
+            "bearingMagnetic" => update_value(&mut self.bearing_magnetic, value, info),
+            "velocityMadeGood" => update_value(&mut self.velocity_made_good, value, info),
+            "timeToGo" => update_value(&mut self.time_to_go, value, info),
+            "targetSpeed" => update_value(&mut self.target_speed, value, info),
             "previousPoint" => {
                 self.previous_point = V1CourseCalculationsPreviousPoint::from_value(value)
             }
@@ -257,13 +242,7 @@ impl V1CourseCalculationsPreviousPoint {
         if value.is_null() {
             None
         } else {
-            let route_result: Result<V1CourseCalculationsPreviousPoint, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(route_value) = route_result {
-                Some(route_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -351,8 +330,15 @@ impl V1Course {
         V1CourseBuilder::default()
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
+            "crossTrackError" | "bearingTrackTrue" | "bearingTrackMagnetic"
+                if info.keeps_value() => {}
             "crossTrackError" => {
                 self.cross_track_error = Some(V1NumberValue::builder().json_value(value).build());
             }
@@ -369,7 +355,7 @@ impl V1Course {
                 }
                 if let Some(ref mut route) = self.active_route {
                     path.remove(0);
-                    route.update(path, value);
+                    route.update(path, value, info);
                 }
             }
             "nextPoint" => {
@@ -378,7 +364,7 @@ impl V1Course {
                 }
                 if let Some(ref mut netxt_point) = self.next_point {
                     path.remove(0);
-                    netxt_point.update(path, value);
+                    netxt_point.update(path, value, info);
                 }
             }
             "previousPoint" => {
@@ -387,7 +373,7 @@ impl V1Course {
                 }
                 if let Some(ref mut netxt_point) = self.previous_point {
                     path.remove(0);
-                    netxt_point.update(path, value);
+                    netxt_point.update(path, value, info);
                 }
             }
             &_ => {
@@ -506,17 +492,19 @@ impl V1ActiveRoute {
         if value.is_null() {
             None
         } else {
-            let route_result: Result<V1ActiveRoute, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(route_value) = route_result {
-                Some(route_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if info.keeps_value() {
+            return;
+        }
         match path[0] {
             "href" => {
                 let type_result: Result<V1StringValue, serde_json::Error> =
@@ -632,7 +620,12 @@ impl Path<f64> for V1CourseNextPoint {
 }
 
 impl V1CourseNextPoint {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
             "value" => {
                 if self.value.is_none() {
@@ -640,39 +633,39 @@ impl V1CourseNextPoint {
                 }
                 if let Some(ref mut point_value) = self.value {
                     path.remove(0);
-                    point_value.update(path, value);
+                    point_value.update(path, value, info);
                 }
             }
             "distance" => {
-                self.distance = V1NumberValue::from_value(value);
+                update_value(&mut self.distance, value, info);
             }
             "bearingTrue" => {
-                self.bearing_true = V1NumberValue::from_value(value);
+                update_value(&mut self.bearing_true, value, info);
             }
             "bearingMagnetic" => {
-                self.bearing_magnetic = V1NumberValue::from_value(value);
+                update_value(&mut self.bearing_magnetic, value, info);
             }
             "velocityMadeGood" => {
-                self.velocity_made_good = V1NumberValue::from_value(value);
+                update_value(&mut self.velocity_made_good, value, info);
             }
             "timeToGo" => {
-                self.time_to_go = V1NumberValue::from_value(value);
+                update_value(&mut self.time_to_go, value, info);
             }
             "position" => {
                 if path.len() == 1 {
-                    self.position = V1PositionType::from_value(value);
+                    update_value(&mut self.position, value, info);
                 } else {
                     if self.position.is_none() {
                         self.position = Some(V1PositionType::default());
                     }
                     if let Some(ref mut position) = self.position {
                         path.remove(0);
-                        position.update(path, value);
+                        position.update(path, value, info);
                     }
                 }
             }
             "arrivalCircle" => {
-                self.arrival_circle = V1NumberValue::from_value(value);
+                update_value(&mut self.arrival_circle, value, info);
             }
             &_ => {
                 log::warn!(
@@ -697,10 +690,10 @@ pub struct V1CourseNextPointValue {
 }
 
 impl V1CourseNextPointValue {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::Value) {
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::Value, info: &V1UpdateInfo) {
         match path[0] {
-            "type" => self.type_ = V1StringValue::from_value(value),
-            "href" => self.href = V1StringValue::from_value(value),
+            "type" => update_value(&mut self.type_, value, info),
+            "href" => update_value(&mut self.href, value, info),
             &_ => {
                 log::warn!(
                     "V1CourseNextPointValue: Unknown value to update {:?}::{:?}",
@@ -734,7 +727,12 @@ impl Path<f64> for V1CoursePreviousPoint {
 }
 
 impl V1CoursePreviousPoint {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
             "value" => {
                 if self.value.is_none() {
@@ -742,22 +740,22 @@ impl V1CoursePreviousPoint {
                 }
                 if let Some(ref mut point_value) = self.value {
                     path.remove(0);
-                    point_value.update(path, value);
+                    point_value.update(path, value, info);
                 }
             }
             "distance" => {
-                self.distance = V1NumberValue::from_value(value);
+                update_value(&mut self.distance, value, info);
             }
             "position" => {
                 if path.len() == 1 {
-                    self.position = V1PositionType::from_value(value);
+                    update_value(&mut self.position, value, info);
                 } else {
                     if self.position.is_none() {
                         self.position = Some(V1PositionType::default());
                     }
                     if let Some(ref mut position) = self.position {
                         path.remove(0);
-                        position.update(path, value);
+                        position.update(path, value, info);
                     }
                 }
             }
@@ -785,10 +783,10 @@ pub struct V1CoursePreviousPointValue {
 }
 
 impl V1CoursePreviousPointValue {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::Value) {
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::Value, info: &V1UpdateInfo) {
         match path[0] {
-            "type" => self.type_ = V1StringValue::from_value(value),
-            "href" => self.href = V1StringValue::from_value(value),
+            "type" => update_value(&mut self.type_, value, info),
+            "href" => update_value(&mut self.href, value, info),
             &_ => {
                 log::warn!(
                     "V1CoursePreviousPointValue: Unknown value to update {:?}::{:?}",
@@ -913,8 +911,7 @@ mod tests {
     fn get_path_from_course_api(path_string: &str) -> Result<f64, SignalKGetError> {
         let course_with_point = make_course_api();
         let path: Vec<&str> = path_string.split('.').collect();
-        let result = get_path(&path, &Some(&course_with_point));
-        result
+        get_path(&path, &Some(&course_with_point))
     }
 
     #[test]
@@ -1,5 +1,6 @@
+use crate::definitions::update_value;
 use crate::helper_functions::{get_f64_value, get_path, Path};
-use crate::{SignalKGetError, V1CommonValueFields, V1NumberValue};
+use crate::{SignalKGetError, V1CommonValueFields, V1NumberValue, V1UpdateInfo};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
@@ -42,8 +43,14 @@ impl V1gnss {
     pub fn builder() -> V1gnssBuilder {
         V1gnssBuilder::default()
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         match path[0] {
+            "type" | "methodQuality" | "integrity" | "satellitesInView" if info.keeps_value() => {}
             "type" => {
                 let type_result: Result<V1gnssType, serde_json::Error> =
                     serde_json::from_value(value.clone());
@@ -74,9 +81,7 @@ impl V1gnss {
                     self.integrity = None;
                 }
             }
-            "satellites" => {
-                self.satellites = Some(V1NumberValue::builder().json_value(value).build())
-            }
+            "satellites" => update_value(&mut self.satellites, value, info),
             "satellitesInView" => {
                 let satellites_in_view_result: Result<V1gnssSatellitesInView, serde_json::Error> =
                     serde_json::from_value(value.clone());
@@ -90,21 +95,11 @@ impl V1gnss {
                     self.satellites_in_view = None;
                 }
             }
-            "antennaAltitude" => {
-                self.antenna_altitude = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "horizontalDilution" => {
-                self.horizontal_dilution = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "positionDilution" => {
-                self.position_dilution = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "geoidalSeparation" => {
-                self.geoidal_separation = Some(V1NumberValue::builder().json_value(value).build())
-            }
-            "differentialAge" => {
-                self.differential_age = Some(V1NumberValue::builder().json_value(value).build())
-            }
+            "antennaAltitude" => update_value(&mut self.antenna_altitude, value, info),
+            "horizontalDilution" => update_value(&mut self.horizontal_dilution, value, info),
+            "positionDilution" => update_value(&mut self.position_dilution, value, info),
+            "geoidalSeparation" => update_value(&mut self.geoidal_separation, value, info),
+            "differentialAge" => update_value(&mut self.differential_age, value, info),
             &_ => {
                 log::warn!("V1gnss: Unknown value to update: {:?}::{:?}", path, value);
             }
@@ -255,7 +250,14 @@ impl Path<f64> for V1gnssSatellitesInViewValue {
         } else {
             match path[0] {
                 "count" => Err(SignalKGetError::WrongDataType),
-                "satellites" => Err(SignalKGetError::TBD),
+                "satellites" if path.len() > 2 => {
+                    let satellite = path[1]
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| self.satellites.as_ref()?.get(index));
+                    get_path(&path[1..], &satellite)
+                }
+                "satellites" => Err(SignalKGetError::WrongDataType),
                 &_ => Err(SignalKGetError::NoSuchPath),
             }
         }
@@ -271,6 +273,24 @@ pub struct V1gnssSatellite {
     signal_to_noise_ratio: Option<i64>,
 }
 
+impl Path<f64> for V1gnssSatellite {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "id" => self
+                .id
+                .map(|id| id as f64)
+                .ok_or(SignalKGetError::ValueNotSet),
+            "elevation" => self.elevation.ok_or(SignalKGetError::ValueNotSet),
+            "azimuth" => self.azimuth.ok_or(SignalKGetError::ValueNotSet),
+            "SNR" => self
+                .signal_to_noise_ratio
+                .map(|snr| snr as f64)
+                .ok_or(SignalKGetError::ValueNotSet),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct V1gnssBuilder {}
 
@@ -0,0 +1,307 @@
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::definitions::{update_value, V1NumberValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, get_path, Path};
+use crate::navigation::V1PositionType;
+use crate::SignalKGetError;
+
+/// Specific navigational data related to yacht racing
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Racing {
+    /// Position of starboard start mark
+    pub start_line_stb: Option<V1PositionType>,
+    /// Position of port start mark
+    pub start_line_port: Option<V1PositionType>,
+    /// The current distance to the start line
+    pub distance_startline: Option<V1NumberValue>,
+    /// Time left before start
+    pub time_to_start: Option<V1NumberValue>,
+    /// Time to arrive at the start line on port, turning downwind
+    pub time_port_down: Option<V1NumberValue>,
+    /// Time to arrive at the start line on port, turning upwind
+    pub time_port_up: Option<V1NumberValue>,
+    /// Time to arrive at the start line on starboard, turning downwind
+    pub time_stbd_down: Option<V1NumberValue>,
+    /// Time to arrive at the start line on starboard, turning upwind
+    pub time_stbd_up: Option<V1NumberValue>,
+    /// The layline crossing the current course
+    pub layline: Option<V1RacingLayline>,
+    /// The layline parallel to current course
+    pub opposite_layline: Option<V1RacingLayline>,
+}
+
+impl Path<f64> for V1Racing {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        debug!("V1Racing::get_path({:?})", path);
+        match path[0] {
+            "startLineStb" => get_path(path, &(self.start_line_stb.as_ref())),
+            "startLinePort" => get_path(path, &(self.start_line_port.as_ref())),
+            "distanceStartline" => get_f64_value(&self.distance_startline),
+            "timeToStart" => get_f64_value(&self.time_to_start),
+            "timePortDown" => get_f64_value(&self.time_port_down),
+            "timePortUp" => get_f64_value(&self.time_port_up),
+            "timeStbdDown" => get_f64_value(&self.time_stbd_down),
+            "timeStbdUp" => get_f64_value(&self.time_stbd_up),
+            "layline" => get_path(path, &(self.layline.as_ref())),
+            "oppositeLayline" => get_path(path, &(self.opposite_layline.as_ref())),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1Racing {
+    pub fn builder() -> V1RacingBuilder {
+        V1RacingBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "startLineStb" => update_value(&mut self.start_line_stb, value, info),
+            "startLinePort" => update_value(&mut self.start_line_port, value, info),
+            "distanceStartline" => update_value(&mut self.distance_startline, value, info),
+            "timeToStart" => update_value(&mut self.time_to_start, value, info),
+            "timePortDown" => update_value(&mut self.time_port_down, value, info),
+            "timePortUp" => update_value(&mut self.time_port_up, value, info),
+            "timeStbdDown" => update_value(&mut self.time_stbd_down, value, info),
+            "timeStbdUp" => update_value(&mut self.time_stbd_up, value, info),
+            "layline" => {
+                if self.layline.is_none() {
+                    self.layline = Some(V1RacingLayline::default());
+                }
+                if let Some(ref mut layline) = self.layline {
+                    path.remove(0);
+                    layline.update(path, value, info);
+                }
+            }
+            "oppositeLayline" => {
+                if self.opposite_layline.is_none() {
+                    self.opposite_layline = Some(V1RacingLayline::default());
+                }
+                if let Some(ref mut layline) = self.opposite_layline {
+                    path.remove(0);
+                    layline.update(path, value, info);
+                }
+            }
+            &_ => {
+                log::warn!("V1Racing: Unknown value to update: {:?}::{:?}", path, value);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1RacingBuilder {
+    start_line_stb: Option<V1PositionType>,
+    start_line_port: Option<V1PositionType>,
+    distance_startline: Option<V1NumberValue>,
+    time_to_start: Option<V1NumberValue>,
+    time_port_down: Option<V1NumberValue>,
+    time_port_up: Option<V1NumberValue>,
+    time_stbd_down: Option<V1NumberValue>,
+    time_stbd_up: Option<V1NumberValue>,
+    layline: Option<V1RacingLayline>,
+    opposite_layline: Option<V1RacingLayline>,
+}
+
+impl V1RacingBuilder {
+    pub fn start_line_stb(mut self, value: V1PositionType) -> V1RacingBuilder {
+        self.start_line_stb = Some(value);
+        self
+    }
+    pub fn start_line_port(mut self, value: V1PositionType) -> V1RacingBuilder {
+        self.start_line_port = Some(value);
+        self
+    }
+    pub fn distance_startline(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.distance_startline = Some(value);
+        self
+    }
+    pub fn time_to_start(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.time_to_start = Some(value);
+        self
+    }
+    pub fn time_port_down(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.time_port_down = Some(value);
+        self
+    }
+    pub fn time_port_up(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.time_port_up = Some(value);
+        self
+    }
+    pub fn time_stbd_down(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.time_stbd_down = Some(value);
+        self
+    }
+    pub fn time_stbd_up(mut self, value: V1NumberValue) -> V1RacingBuilder {
+        self.time_stbd_up = Some(value);
+        self
+    }
+    pub fn layline(mut self, value: V1RacingLayline) -> V1RacingBuilder {
+        self.layline = Some(value);
+        self
+    }
+    pub fn opposite_layline(mut self, value: V1RacingLayline) -> V1RacingBuilder {
+        self.opposite_layline = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Racing {
+        V1Racing {
+            start_line_stb: self.start_line_stb,
+            start_line_port: self.start_line_port,
+            distance_startline: self.distance_startline,
+            time_to_start: self.time_to_start,
+            time_port_down: self.time_port_down,
+            time_port_up: self.time_port_up,
+            time_stbd_down: self.time_stbd_down,
+            time_stbd_up: self.time_stbd_up,
+            layline: self.layline,
+            opposite_layline: self.opposite_layline,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1RacingLayline {
+    /// The current distance to the layline
+    pub distance: Option<V1NumberValue>,
+    /// The time to the layline at current speed and heading
+    pub time: Option<V1NumberValue>,
+}
+
+impl Path<f64> for V1RacingLayline {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "distance" => get_f64_value(&self.distance),
+            "time" => get_f64_value(&self.time),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1RacingLayline {
+    pub fn builder() -> V1RacingLaylineBuilder {
+        V1RacingLaylineBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "distance" => update_value(&mut self.distance, value, info),
+            "time" => update_value(&mut self.time, value, info),
+            &_ => {
+                log::warn!(
+                    "V1RacingLayline: Unknown value to update: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1RacingLaylineBuilder {
+    distance: Option<V1NumberValue>,
+    time: Option<V1NumberValue>,
+}
+
+impl V1RacingLaylineBuilder {
+    pub fn distance(mut self, value: V1NumberValue) -> V1RacingLaylineBuilder {
+        self.distance = Some(value);
+        self
+    }
+    pub fn time(mut self, value: V1NumberValue) -> V1RacingLaylineBuilder {
+        self.time = Some(value);
+        self
+    }
+    pub fn build(self) -> V1RacingLayline {
+        V1RacingLayline {
+            distance: self.distance,
+            time: self.time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod racing_tests {
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::navigation_racing::{V1Racing, V1RacingLayline};
+    use crate::{SignalKGetError, V1FullFormat, V1Navigation, V1NumberValue, V1UpdateInfo};
+
+    fn racing_sample() -> V1FullFormat {
+        let file =
+            File::open("tests/specification/test_data/full-valid/racing-sample.json").unwrap();
+        serde_json::from_reader(BufReader::new(file)).unwrap()
+    }
+
+    #[test]
+    fn racing_from_sample() {
+        let full = racing_sample();
+        assert_eq!(
+            full.get_f64_for_path("self.navigation.racing.startLinePort.latitude".into()),
+            Ok(49.297333333333336)
+        );
+        assert_eq!(
+            full.get_f64_for_path("self.navigation.racing.timeToStart".into()),
+            Ok(52.0)
+        );
+        assert_eq!(
+            full.get_f64_for_path("self.navigation.racing.oppositeLayline.distance".into()),
+            Ok(18.0)
+        );
+        assert_eq!(
+            full.get_f64_for_path("self.navigation.racing.layline.speed".into()),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn racing_round_trip_through_deltas() {
+        let full = racing_sample();
+        let mut copy = V1FullFormat::builder().self_(full.self_.clone()).build();
+        for delta in full.to_deltas() {
+            copy.apply_delta(&delta);
+        }
+        assert_eq!(
+            copy.get_self().unwrap().navigation.as_ref().unwrap().racing,
+            full.get_self().unwrap().navigation.as_ref().unwrap().racing
+        );
+    }
+
+    #[test]
+    fn update_racing_layline() {
+        let mut navigation = V1Navigation::default();
+        let info = V1UpdateInfo::default();
+        navigation.update(&mut vec!["racing", "layline", "time"], &json!(42.0), &info);
+        navigation.update(&mut vec!["racing", "timeToStart"], &json!(120.0), &info);
+        assert_eq!(
+            navigation.racing,
+            Some(
+                V1Racing::builder()
+                    .time_to_start(V1NumberValue::builder().value(120.0).build())
+                    .layline(
+                        V1RacingLayline::builder()
+                            .time(V1NumberValue::builder().value(42.0).build())
+                            .build()
+                    )
+                    .build()
+            )
+        );
+    }
+}
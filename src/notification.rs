@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::definitions::V1CommonValueFields;
+use crate::definitions::{V1CommonValueFields, V1Meta, V1MetaZone};
+use crate::helper_functions::Path;
+use crate::{SignalKGetError, V1UpdateInfo};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct V1Notification {
@@ -17,6 +19,115 @@ impl V1Notification {
     pub fn builder() -> V1NotificationBuilder {
         V1NotificationBuilder::default()
     }
+
+    /// Every raised notification in this tree with its path, sorted by path
+    pub fn notifications(&self) -> Vec<(String, &V1NotificationValue)> {
+        let mut notifications = Vec::new();
+        self.collect_notifications("", &mut notifications);
+        notifications.sort_by(|a, b| a.0.cmp(&b.0));
+        notifications
+    }
+
+    /// The notification raised at a path below this one, e.g. `navigation.anchor`
+    pub fn get(&self, path: &str) -> Option<&V1NotificationValue> {
+        path.split('.')
+            .try_fold(self, |notification, key| notification.childs.get(key))?
+            .value
+            .as_ref()
+    }
+
+    /// The raised notifications with a state, e.g. `alarm`
+    pub fn with_state(&self, state: &str) -> Vec<(String, &V1NotificationValue)> {
+        self.notifications()
+            .into_iter()
+            .filter(|(_, notification)| notification.state == state)
+            .collect()
+    }
+
+    /// The notifications in an `alert`, `warn`, `alarm` or `emergency` state
+    pub fn active(&self) -> Vec<(String, &V1NotificationValue)> {
+        self.notifications()
+            .into_iter()
+            .filter(|(_, notification)| notification.is_active())
+            .collect()
+    }
+
+    fn collect_notifications<'a>(
+        &'a self,
+        prefix: &str,
+        notifications: &mut Vec<(String, &'a V1NotificationValue)>,
+    ) {
+        if let Some(ref value) = self.value {
+            notifications.push((prefix.to_string(), value));
+        }
+        for (key, child) in &self.childs {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            child.collect_notifications(&path, notifications);
+        }
+    }
+
+    /// A notification without a value and without children
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.childs.is_empty()
+    }
+
+    /// Raise or clear the notification at a path below this one
+    ///
+    /// A `null` value clears the notification, branches left without
+    /// notifications are removed. Meta data is attached to a raised
+    /// notification.
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::Value, info: &V1UpdateInfo) {
+        if path.is_empty() {
+            if info.meta_only && self.value.is_some() {
+                self.common_value_fields
+                    .get_or_insert_with(V1CommonValueFields::default)
+                    .set_update_info(info);
+            }
+            if info.keeps_value() {
+                return;
+            }
+            if value.is_null() {
+                self.value = None;
+                self.common_value_fields = None;
+                return;
+            }
+            match serde_json::from_value(value.clone()) {
+                Ok(notification) => {
+                    self.value = Some(notification);
+                    self.common_value_fields
+                        .get_or_insert_with(V1CommonValueFields::default)
+                        .set_update_info(info);
+                }
+                Err(_) => log::warn!("V1Notification: Not a notification: {:?}", value),
+            }
+            return;
+        }
+        let key = path.remove(0).to_string();
+        let child = self.childs.entry(key.clone()).or_default();
+        child.update(path, value, info);
+        if child.is_empty() {
+            self.childs.remove(&key);
+        }
+    }
+}
+
+impl Path<f64> for V1Notification {
+    fn get_path(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        match path.split_first() {
+            None => Err(SignalKGetError::WrongDataType),
+            Some((key, rest)) => match self.childs.get(*key) {
+                Some(child) => child.get_path(rest),
+                None if *key == "value" && self.value.is_some() => {
+                    Err(SignalKGetError::WrongDataType)
+                }
+                None => Err(SignalKGetError::NoSuchPath),
+            },
+        }
+    }
 }
 
 #[derive(Default)]
@@ -60,6 +171,32 @@ impl V1NotificationValue {
     pub fn builder() -> V1NotificationValueBuilder {
         V1NotificationValueBuilder::default()
     }
+
+    /// Check if the state is `alert`, `warn`, `alarm` or `emergency`
+    pub fn is_active(&self) -> bool {
+        ["alert", "warn", "alarm", "emergency"].contains(&self.state.as_str())
+    }
+
+    /// The notification for a value of `path` that entered a zone
+    ///
+    /// Without a zone message the message names the value and the state.
+    pub fn from_zone(
+        path: &str,
+        zone: &V1MetaZone,
+        meta: &V1Meta,
+        timestamp: Option<String>,
+    ) -> Self {
+        let name = meta.display_name.as_deref().unwrap_or(path);
+        Self {
+            method: meta.methods_for(&zone.state),
+            state: zone.state.clone(),
+            message: zone
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("{} is in {} zone", name, zone.state)),
+            timestamp,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -83,6 +220,10 @@ impl V1NotificationValueBuilder {
         self.message = value;
         self
     }
+    pub fn timestamp(mut self, value: String) -> V1NotificationValueBuilder {
+        self.timestamp = Some(value);
+        self
+    }
     pub fn build(self) -> V1NotificationValue {
         V1NotificationValue {
             method: self.method,
@@ -1,4 +1,5 @@
-use crate::definitions::{V1StringValue, V2NumberValue};
+use crate::definitions::{update_value, V1StringValue, V2NumberValue};
+use crate::delta::V1UpdateInfo;
 use crate::helper_functions::{get_f64_value, F64CompatiblePath};
 use crate::{SignalKGetError, V1CommonValueFields, V1NumberValue, V1Source};
 use serde::{Deserialize, Serialize};
@@ -53,39 +54,40 @@ impl F64CompatiblePath for V1Performance {
 }
 
 impl V1Performance {
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         if path.is_empty() {
             return;
         }
         match path[0] {
-            // "polars" => self.polars = V1NumberValue::from_value(value),
-            "activePolar" => self.active_polar = V1StringValue::from_value(value),
-            // "activePolarData" => self.active_polar_data = V1NumberValue::from_value(value),
-            "polarSpeed" => self.polar_speed = V1NumberValue::from_value(value),
-            "polarSpeedRatio" => self.polar_speed_ratio = V1NumberValue::from_value(value),
-            "velocityMadeGood" => self.velocity_made_good = V1NumberValue::from_value(value),
+            // "polars" => update_value(&mut self.polars, value, info),
+            "activePolar" => update_value(&mut self.active_polar, value, info),
+            // "activePolarData" => update_value(&mut self.active_polar_data, value, info),
+            "polarSpeed" => update_value(&mut self.polar_speed, value, info),
+            "polarSpeedRatio" => update_value(&mut self.polar_speed_ratio, value, info),
+            "velocityMadeGood" => update_value(&mut self.velocity_made_good, value, info),
             "velocityMadeGoodToWaypoint" => {
-                self.velocity_made_good_to_waypoint = V1NumberValue::from_value(value)
+                update_value(&mut self.velocity_made_good_to_waypoint, value, info)
             }
-            "beatAngle" => self.beat_angle = V1NumberValue::from_value(value),
+            "beatAngle" => update_value(&mut self.beat_angle, value, info),
             "beatAngleVelocityMadeGood" => {
-                self.beat_angle_velocity_made_good = V1NumberValue::from_value(value)
+                update_value(&mut self.beat_angle_velocity_made_good, value, info)
             }
-            "beatAngleTargetSpeed" => {
-                self.beat_angle_target_speed = V1NumberValue::from_value(value)
-            }
-            "gybeAngle" => self.gybe_angle = V1NumberValue::from_value(value),
+            "beatAngleTargetSpeed" => update_value(&mut self.beat_angle_target_speed, value, info),
+            "gybeAngle" => update_value(&mut self.gybe_angle, value, info),
             "gybeAngleVelocityMadeGood" => {
-                self.gybe_angle_velocity_made_good = V1NumberValue::from_value(value)
-            }
-            "gybeAngleTargetSpeed" => {
-                self.gybe_angle_target_speed = V1NumberValue::from_value(value)
+                update_value(&mut self.gybe_angle_velocity_made_good, value, info)
             }
-            "targetAngle" => self.target_angle = V1NumberValue::from_value(value),
-            "targetSpeed" => self.target_speed = V1NumberValue::from_value(value),
-            "leeway" => self.leeway = V1NumberValue::from_value(value),
-            "tackMagnetic" => self.tack_magnetic = V1NumberValue::from_value(value),
-            "tackTrue" => self.tack_true = V1NumberValue::from_value(value),
+            "gybeAngleTargetSpeed" => update_value(&mut self.gybe_angle_target_speed, value, info),
+            "targetAngle" => update_value(&mut self.target_angle, value, info),
+            "targetSpeed" => update_value(&mut self.target_speed, value, info),
+            "leeway" => update_value(&mut self.leeway, value, info),
+            "tackMagnetic" => update_value(&mut self.tack_magnetic, value, info),
+            "tackTrue" => update_value(&mut self.tack_true, value, info),
 
             &_ => {
                 log::warn!(
@@ -112,13 +114,7 @@ impl V1PolarUuid {
         if value.is_null() {
             None
         } else {
-            let ship_type_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(ship_type_value) = ship_type_result {
-                Some(ship_type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
@@ -138,13 +134,7 @@ impl V1Polar {
         if value.is_null() {
             None
         } else {
-            let ship_type_result: Result<Self, serde_json::Error> =
-                serde_json::from_value(value.clone());
-            if let Ok(ship_type_value) = ship_type_result {
-                Some(ship_type_value)
-            } else {
-                None
-            }
+            serde_json::from_value(value.clone()).ok()
         }
     }
 }
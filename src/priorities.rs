@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use time::{Duration, OffsetDateTime};
+
+use crate::V1UpdateInfo;
+
+/// A source allowed to provide the value of a path
+///
+/// The `timeout` is the number of milliseconds a source with higher priority
+/// has to be silent before this source takes over the path.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1SourcePriority {
+    pub source_ref: String,
+    pub timeout: i64,
+}
+
+impl V1SourcePriority {
+    pub fn new(source_ref: &str, timeout: i64) -> Self {
+        Self {
+            source_ref: source_ref.to_string(),
+            timeout,
+        }
+    }
+}
+
+/// Source priorities per path, and the source currently providing each path
+///
+/// Works like the source priorities of the Signal K server. The current value
+/// of a path is only replaced by a source with the same or higher priority
+/// than the source that wrote it. A source with lower priority takes over when
+/// the current source has not been heard from within the timeout of the lower
+/// source. Sources not in the list have the lowest priority and use the
+/// longest timeout in the list. Updates without a timestamp can not take over.
+#[derive(Debug, Default)]
+pub struct SourcePriorities {
+    paths: HashMap<String, Vec<V1SourcePriority>>,
+    current: HashMap<(String, String), (String, Option<OffsetDateTime>)>,
+}
+
+impl SourcePriorities {
+    /// Set the sources for a path, the first one has the highest priority
+    ///
+    /// An empty list removes the priorities for the path.
+    pub fn set(&mut self, path: &str, priorities: Vec<V1SourcePriority>) {
+        if priorities.is_empty() {
+            self.paths.remove(path);
+        } else {
+            self.paths.insert(path.to_string(), priorities);
+        }
+        self.current.retain(|(_, p), _| p != path);
+    }
+
+    pub fn get(&self, path: &str) -> Option<&Vec<V1SourcePriority>> {
+        self.paths.get(path)
+    }
+
+    /// Decide if an update should replace the current value of a path
+    ///
+    /// Updates that are not accepted should only be kept in the `values` of
+    /// the path.
+    pub fn accept(&mut self, context: &str, path: &str, info: &V1UpdateInfo) -> bool {
+        let Some(priorities) = self.paths.get(path) else {
+            return true;
+        };
+        let source = info.ref_source.clone().unwrap_or_default();
+        let time = info.time();
+        let key = (context.to_string(), path.to_string());
+        let accepted = match self.current.get(&key) {
+            None => true,
+            Some((current_source, _)) if *current_source == source => true,
+            Some((current_source, current_time)) => {
+                let rank = |source: &str| {
+                    priorities
+                        .iter()
+                        .position(|priority| priority.source_ref == source)
+                        .unwrap_or(priorities.len())
+                };
+                let timeout = priorities
+                    .get(rank(&source))
+                    .map(|priority| priority.timeout)
+                    .unwrap_or_else(|| {
+                        priorities
+                            .iter()
+                            .map(|priority| priority.timeout)
+                            .max()
+                            .unwrap_or_default()
+                    });
+                let timed_out = match (time, current_time) {
+                    (Some(time), Some(current_time)) => {
+                        time - *current_time > Duration::milliseconds(timeout)
+                    }
+                    _ => false,
+                };
+                rank(&source) <= rank(current_source) || timed_out
+            }
+        };
+        if accepted {
+            // Without a timestamp the source was last heard at its previous update
+            let time = time.or_else(|| {
+                self.current
+                    .get(&key)
+                    .filter(|(current_source, _)| *current_source == source)
+                    .and_then(|(_, current_time)| *current_time)
+            });
+            self.current.insert(key, (source, time));
+        }
+        accepted
+    }
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use crate::definitions::V1NumberValue;
-use crate::V1CommonValueFields;
+use crate::definitions::{add_source_value, update_value, UpdatableValue, V1NumberValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, get_f64_value_for_path, F64CompatiblePath};
+use crate::{SignalKGetError, V1CommonValueFields, V1SourceValue};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -20,13 +23,121 @@ pub struct V1Propulsion {
     pub intake_manifold_temperature: Option<V1NumberValue>,
     pub engine_load: Option<V1NumberValue>,
     pub engine_torque: Option<V1NumberValue>,
-    // pub transmission: Option<V1Transmission>,
+    pub exhaust_temperature: Option<V1NumberValue>,
+    pub transmission: Option<V1PropulsionTransmission>,
+    pub drive: Option<V1PropulsionDrive>,
+    pub fuel: Option<V1PropulsionFuel>,
+}
+
+impl F64CompatiblePath for V1Propulsion {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        match path[0] {
+            "label" => Err(SignalKGetError::WrongDataType),
+            "state" => Err(SignalKGetError::WrongDataType),
+            "revolutions" => get_f64_value(&self.revolutions),
+            "temperature" => get_f64_value(&self.temperature),
+            "oilTemperature" => get_f64_value(&self.oil_temperature),
+            "oilPressure" => get_f64_value(&self.oil_pressure),
+            "alternatorVoltage" => get_f64_value(&self.alternator_voltage),
+            "runTime" => get_f64_value(&self.run_time),
+            "coolantTemperature" => get_f64_value(&self.coolant_temperature),
+            "coolantPressure" => get_f64_value(&self.coolant_pressure),
+            "boostPressure" => get_f64_value(&self.boost_pressure),
+            "intakeManifoldTemperature" => get_f64_value(&self.intake_manifold_temperature),
+            "engineLoad" => get_f64_value(&self.engine_load),
+            "engineTorque" => get_f64_value(&self.engine_torque),
+            "exhaustTemperature" => get_f64_value(&self.exhaust_temperature),
+            "transmission" => get_f64_value_for_path(path, &self.transmission),
+            "drive" => get_f64_value_for_path(path, &self.drive),
+            "fuel" => get_f64_value_for_path(path, &self.fuel),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
 }
 
 impl V1Propulsion {
     pub fn builder() -> V1PropulsionBuilder {
         V1PropulsionBuilder::default()
     }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "label" => {
+                if !info.keeps_value() {
+                    self.label = value.as_str().map(|s| s.to_string());
+                }
+            }
+            "state" => update_value(&mut self.state, value, info),
+            "revolutions" => update_value(&mut self.revolutions, value, info),
+            "temperature" => update_value(&mut self.temperature, value, info),
+            "oilTemperature" => update_value(&mut self.oil_temperature, value, info),
+            "oilPressure" => update_value(&mut self.oil_pressure, value, info),
+            "alternatorVoltage" => update_value(&mut self.alternator_voltage, value, info),
+            "runTime" => update_value(&mut self.run_time, value, info),
+            "coolantTemperature" => update_value(&mut self.coolant_temperature, value, info),
+            "coolantPressure" => update_value(&mut self.coolant_pressure, value, info),
+            "boostPressure" => update_value(&mut self.boost_pressure, value, info),
+            "intakeManifoldTemperature" => {
+                update_value(&mut self.intake_manifold_temperature, value, info)
+            }
+            "engineLoad" => update_value(&mut self.engine_load, value, info),
+            "engineTorque" => update_value(&mut self.engine_torque, value, info),
+            "exhaustTemperature" => update_value(&mut self.exhaust_temperature, value, info),
+            "transmission" => {
+                if self.transmission.is_none() {
+                    self.transmission = Some(V1PropulsionTransmission::default());
+                }
+                if let Some(ref mut transmission) = self.transmission {
+                    path.remove(0);
+                    transmission.update(path, value, info);
+                }
+            }
+            "drive" => {
+                if self.drive.is_none() {
+                    self.drive = Some(V1PropulsionDrive::default());
+                }
+                if let Some(ref mut drive) = self.drive {
+                    path.remove(0);
+                    drive.update(path, value, info);
+                }
+            }
+            "fuel" => {
+                if self.fuel.is_none() {
+                    self.fuel = Some(V1PropulsionFuel::default());
+                }
+                if let Some(ref mut fuel) = self.fuel {
+                    path.remove(0);
+                    fuel.update(path, value, info);
+                }
+            }
+            "" => {
+                if let serde_json::Value::Object(ref map) = value {
+                    for (k, v) in map.iter() {
+                        let mut path = vec![k.as_str()];
+                        self.update(&mut path, v, info);
+                    }
+                }
+            }
+            &_ => {
+                log::warn!(
+                    "V1Propulsion: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -45,6 +156,10 @@ pub struct V1PropulsionBuilder {
     intake_manifold_temperature: Option<V1NumberValue>,
     engine_load: Option<V1NumberValue>,
     engine_torque: Option<V1NumberValue>,
+    exhaust_temperature: Option<V1NumberValue>,
+    transmission: Option<V1PropulsionTransmission>,
+    drive: Option<V1PropulsionDrive>,
+    fuel: Option<V1PropulsionFuel>,
 }
 
 impl V1PropulsionBuilder {
@@ -110,6 +225,25 @@ impl V1PropulsionBuilder {
         self.engine_torque = Some(engine_torque);
         self
     }
+    pub fn exhaust_temperature(
+        mut self,
+        exhaust_temperature: V1NumberValue,
+    ) -> V1PropulsionBuilder {
+        self.exhaust_temperature = Some(exhaust_temperature);
+        self
+    }
+    pub fn transmission(mut self, transmission: V1PropulsionTransmission) -> V1PropulsionBuilder {
+        self.transmission = Some(transmission);
+        self
+    }
+    pub fn drive(mut self, drive: V1PropulsionDrive) -> V1PropulsionBuilder {
+        self.drive = Some(drive);
+        self
+    }
+    pub fn fuel(mut self, fuel: V1PropulsionFuel) -> V1PropulsionBuilder {
+        self.fuel = Some(fuel);
+        self
+    }
     pub fn build(self) -> V1Propulsion {
         V1Propulsion {
             label: self.label,
@@ -126,6 +260,10 @@ impl V1PropulsionBuilder {
             intake_manifold_temperature: self.intake_manifold_temperature,
             engine_load: self.engine_load,
             engine_torque: self.engine_torque,
+            exhaust_temperature: self.exhaust_temperature,
+            transmission: self.transmission,
+            drive: self.drive,
+            fuel: self.fuel,
         }
     }
 }
@@ -137,6 +275,45 @@ pub enum V1PropulsionState {
     Value(V1PropulsionStateValue),
 }
 
+impl Default for V1PropulsionState {
+    fn default() -> Self {
+        V1PropulsionState::Value(V1PropulsionStateValue::default())
+    }
+}
+
+impl UpdatableValue for V1PropulsionState {
+    fn set_value(&mut self, value: &serde_json::Value) {
+        let Ok(state) = serde_json::from_value(value.clone()) else {
+            log::warn!("V1PropulsionState: Invalid state {:?}", value);
+            return;
+        };
+        match self {
+            V1PropulsionState::Expanded(ref mut expanded) => expanded.value = state,
+            V1PropulsionState::Value(ref mut value) => *value = state,
+        }
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if info.is_empty() {
+            return;
+        }
+        if let V1PropulsionState::Value(ref value) = self {
+            *self = V1PropulsionState::Expanded(V1PropulsionExpandedState {
+                value: value.clone(),
+                common_state: None,
+            });
+        }
+        if let V1PropulsionState::Expanded(ref mut expanded) = self {
+            expanded
+                .common_state
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, _value: &serde_json::Value, _info: &V1UpdateInfo) {
+        // The state of an engine only has one source
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct V1PropulsionExpandedState {
@@ -153,3 +330,495 @@ pub enum V1PropulsionStateValue {
     #[default]
     Unusable,
 }
+
+/// The transmission (gear box) of the engine
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1PropulsionTransmission {
+    /// Currently selected gear the engine is in i.e. Forward, Reverse, etc.
+    pub gear: Option<V1PropulsionGear>,
+    /// Gear ratio, engine rotations per propeller shaft rotation
+    pub gear_ratio: Option<V1NumberValue>,
+    /// Oil temperature
+    pub oil_temperature: Option<V1NumberValue>,
+    /// Oil pressure
+    pub oil_pressure: Option<V1NumberValue>,
+}
+
+impl F64CompatiblePath for V1PropulsionTransmission {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "gear" => Err(SignalKGetError::WrongDataType),
+            "gearRatio" => get_f64_value(&self.gear_ratio),
+            "oilTemperature" => get_f64_value(&self.oil_temperature),
+            "oilPressure" => get_f64_value(&self.oil_pressure),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1PropulsionTransmission {
+    pub fn builder() -> V1PropulsionTransmissionBuilder {
+        V1PropulsionTransmissionBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "gear" => update_value(&mut self.gear, value, info),
+            "gearRatio" => update_value(&mut self.gear_ratio, value, info),
+            "oilTemperature" => update_value(&mut self.oil_temperature, value, info),
+            "oilPressure" => update_value(&mut self.oil_pressure, value, info),
+            &_ => {
+                log::warn!(
+                    "V1PropulsionTransmission: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1PropulsionTransmissionBuilder {
+    gear: Option<V1PropulsionGear>,
+    gear_ratio: Option<V1NumberValue>,
+    oil_temperature: Option<V1NumberValue>,
+    oil_pressure: Option<V1NumberValue>,
+}
+
+impl V1PropulsionTransmissionBuilder {
+    pub fn gear(mut self, value: V1PropulsionGear) -> V1PropulsionTransmissionBuilder {
+        self.gear = Some(value);
+        self
+    }
+    pub fn gear_ratio(mut self, value: V1NumberValue) -> V1PropulsionTransmissionBuilder {
+        self.gear_ratio = Some(value);
+        self
+    }
+    pub fn oil_temperature(mut self, value: V1NumberValue) -> V1PropulsionTransmissionBuilder {
+        self.oil_temperature = Some(value);
+        self
+    }
+    pub fn oil_pressure(mut self, value: V1NumberValue) -> V1PropulsionTransmissionBuilder {
+        self.oil_pressure = Some(value);
+        self
+    }
+    pub fn build(self) -> V1PropulsionTransmission {
+        V1PropulsionTransmission {
+            gear: self.gear,
+            gear_ratio: self.gear_ratio,
+            oil_temperature: self.oil_temperature,
+            oil_pressure: self.oil_pressure,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1PropulsionGear {
+    pub value: Option<V1PropulsionGearValue>,
+    #[serde(flatten)]
+    pub common_value_fields: Option<V1CommonValueFields>,
+    pub values: Option<HashMap<String, V1SourceValue<V1PropulsionGearValue>>>,
+}
+
+impl V1PropulsionGear {
+    pub fn new(value: V1PropulsionGearValue) -> V1PropulsionGear {
+        V1PropulsionGear {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl UpdatableValue for V1PropulsionGear {
+    fn set_value(&mut self, value: &serde_json::Value) {
+        self.value = serde_json::from_value(value.clone()).ok();
+    }
+    fn set_update_info(&mut self, info: &V1UpdateInfo) {
+        if !info.is_empty() {
+            self.common_value_fields
+                .get_or_insert_with(V1CommonValueFields::default)
+                .set_update_info(info);
+        }
+    }
+    fn add_source_value(&mut self, value: &serde_json::Value, info: &V1UpdateInfo) {
+        if let Some(ref common) = self.common_value_fields {
+            add_source_value(
+                &mut self.values,
+                &common.source,
+                || common.source_value(self.value.clone()),
+                value,
+                info,
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum V1PropulsionGearValue {
+    Forward,
+    Neutral,
+    Reverse,
+    Fault,
+}
+
+/// Data about the engine's drive
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1PropulsionDrive {
+    /// The type of drive the boat has i.e Outboard, shaft, jet, etc.
+    #[serde(rename = "type")]
+    pub type_: Option<V1PropulsionDriveType>,
+    /// Trim/tilt state, 0 is fully down
+    pub trim_state: Option<V1NumberValue>,
+    /// Current thrust angle for steerable drives, +ve is thrust to starboard
+    pub thrust_angle: Option<V1NumberValue>,
+}
+
+impl F64CompatiblePath for V1PropulsionDrive {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "type" => Err(SignalKGetError::WrongDataType),
+            "trimState" => get_f64_value(&self.trim_state),
+            "thrustAngle" => get_f64_value(&self.thrust_angle),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1PropulsionDrive {
+    pub fn builder() -> V1PropulsionDriveBuilder {
+        V1PropulsionDriveBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "type" => {
+                if !info.keeps_value() {
+                    self.type_ = serde_json::from_value(value.clone()).ok();
+                }
+            }
+            "trimState" => update_value(&mut self.trim_state, value, info),
+            "thrustAngle" => update_value(&mut self.thrust_angle, value, info),
+            &_ => {
+                log::warn!(
+                    "V1PropulsionDrive: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1PropulsionDriveBuilder {
+    type_: Option<V1PropulsionDriveType>,
+    trim_state: Option<V1NumberValue>,
+    thrust_angle: Option<V1NumberValue>,
+}
+
+impl V1PropulsionDriveBuilder {
+    pub fn type_(mut self, value: V1PropulsionDriveType) -> V1PropulsionDriveBuilder {
+        self.type_ = Some(value);
+        self
+    }
+    pub fn trim_state(mut self, value: V1NumberValue) -> V1PropulsionDriveBuilder {
+        self.trim_state = Some(value);
+        self
+    }
+    pub fn thrust_angle(mut self, value: V1NumberValue) -> V1PropulsionDriveBuilder {
+        self.thrust_angle = Some(value);
+        self
+    }
+    pub fn build(self) -> V1PropulsionDrive {
+        V1PropulsionDrive {
+            type_: self.type_,
+            trim_state: self.trim_state,
+            thrust_angle: self.thrust_angle,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum V1PropulsionDriveType {
+    Saildrive,
+    Shaft,
+    Outboard,
+    Jet,
+    Pod,
+    Other,
+}
+
+/// Data about the engine's fuel supply
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1PropulsionFuel {
+    /// Fuel type
+    #[serde(rename = "type")]
+    pub type_: Option<V1PropulsionFuelType>,
+    /// Used fuel since last reset. Resetting is at user discretion
+    pub used: Option<V1NumberValue>,
+    /// Fuel pressure
+    pub pressure: Option<V1NumberValue>,
+    /// Fuel rate of consumption
+    pub rate: Option<V1NumberValue>,
+    /// Economy fuel rate of consumption
+    pub economy_rate: Option<V1NumberValue>,
+    /// Average fuel rate of consumption
+    pub average_rate: Option<V1NumberValue>,
+}
+
+impl F64CompatiblePath for V1PropulsionFuel {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "type" => Err(SignalKGetError::WrongDataType),
+            "used" => get_f64_value(&self.used),
+            "pressure" => get_f64_value(&self.pressure),
+            "rate" => get_f64_value(&self.rate),
+            "economyRate" => get_f64_value(&self.economy_rate),
+            "averageRate" => get_f64_value(&self.average_rate),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+impl V1PropulsionFuel {
+    pub fn builder() -> V1PropulsionFuelBuilder {
+        V1PropulsionFuelBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        match path[0] {
+            "type" => {
+                if !info.keeps_value() {
+                    self.type_ = serde_json::from_value(value.clone()).ok();
+                }
+            }
+            "used" => update_value(&mut self.used, value, info),
+            "pressure" => update_value(&mut self.pressure, value, info),
+            "rate" => update_value(&mut self.rate, value, info),
+            "economyRate" => update_value(&mut self.economy_rate, value, info),
+            "averageRate" => update_value(&mut self.average_rate, value, info),
+            &_ => {
+                log::warn!(
+                    "V1PropulsionFuel: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1PropulsionFuelBuilder {
+    type_: Option<V1PropulsionFuelType>,
+    used: Option<V1NumberValue>,
+    pressure: Option<V1NumberValue>,
+    rate: Option<V1NumberValue>,
+    economy_rate: Option<V1NumberValue>,
+    average_rate: Option<V1NumberValue>,
+}
+
+impl V1PropulsionFuelBuilder {
+    pub fn type_(mut self, value: V1PropulsionFuelType) -> V1PropulsionFuelBuilder {
+        self.type_ = Some(value);
+        self
+    }
+    pub fn used(mut self, value: V1NumberValue) -> V1PropulsionFuelBuilder {
+        self.used = Some(value);
+        self
+    }
+    pub fn pressure(mut self, value: V1NumberValue) -> V1PropulsionFuelBuilder {
+        self.pressure = Some(value);
+        self
+    }
+    pub fn rate(mut self, value: V1NumberValue) -> V1PropulsionFuelBuilder {
+        self.rate = Some(value);
+        self
+    }
+    pub fn economy_rate(mut self, value: V1NumberValue) -> V1PropulsionFuelBuilder {
+        self.economy_rate = Some(value);
+        self
+    }
+    pub fn average_rate(mut self, value: V1NumberValue) -> V1PropulsionFuelBuilder {
+        self.average_rate = Some(value);
+        self
+    }
+    pub fn build(self) -> V1PropulsionFuel {
+        V1PropulsionFuel {
+            type_: self.type_,
+            used: self.used,
+            pressure: self.pressure,
+            rate: self.rate,
+            economy_rate: self.economy_rate,
+            average_rate: self.average_rate,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum V1PropulsionFuelType {
+    Diesel,
+    Petrol,
+    Electric,
+    #[serde(rename = "coal/wood")]
+    CoalWood,
+    Other,
+}
+
+#[cfg(test)]
+mod propulsion_tests {
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::propulsion::{
+        V1PropulsionDriveType, V1PropulsionFuelType, V1PropulsionGear, V1PropulsionGearValue,
+        V1PropulsionState, V1PropulsionStateValue,
+    };
+    use crate::{SignalKGetError, V1FullFormat, V1UpdateInfo, V1Vessel};
+
+    fn propulsion_sample() -> V1Vessel {
+        let file = File::open("tests/specification/test_data/vessel-valid/propulsion-sample.json")
+            .unwrap();
+        serde_json::from_reader(BufReader::new(file)).unwrap()
+    }
+
+    fn get(vessel: &V1Vessel, path: &str) -> Result<f64, SignalKGetError> {
+        vessel.get_f64_for_path(&mut path.split('.').collect())
+    }
+
+    #[test]
+    fn propulsion_paths_from_sample() {
+        let vessel = propulsion_sample();
+        assert_eq!(get(&vessel, "propulsion.instance0.revolutions"), Ok(1280.0));
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.exhaustTemperature"),
+            Ok(420.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.transmission.oilPressure"),
+            Ok(100.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.transmission.oilTemperature"),
+            Ok(310.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.transmission.gearRatio"),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.transmission.gear"),
+            Err(SignalKGetError::WrongDataType)
+        );
+        assert_eq!(get(&vessel, "propulsion.instance0.fuel.rate"), Ok(0.6));
+        assert_eq!(get(&vessel, "propulsion.instance0.fuel.used"), Ok(12.5));
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.fuel.pressure"),
+            Ok(1100.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.drive.trimState"),
+            Ok(99.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0.drive.thrustAngle"),
+            Ok(0.0)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance1.revolutions"),
+            Err(SignalKGetError::NoSuchPath)
+        );
+        assert_eq!(
+            get(&vessel, "propulsion.instance0"),
+            Err(SignalKGetError::WrongDataType)
+        );
+
+        let engine = &vessel.propulsion.as_ref().unwrap()["instance0"];
+        let transmission = engine.transmission.as_ref().unwrap();
+        assert_eq!(
+            transmission.gear.as_ref().unwrap().value,
+            Some(V1PropulsionGearValue::Forward)
+        );
+        assert_eq!(
+            engine.drive.as_ref().unwrap().type_,
+            Some(V1PropulsionDriveType::Outboard)
+        );
+        assert_eq!(
+            engine.fuel.as_ref().unwrap().type_,
+            Some(V1PropulsionFuelType::Diesel)
+        );
+    }
+
+    #[test]
+    fn update_propulsion() {
+        let mut vessel = V1Vessel::default();
+        let info = V1UpdateInfo::default();
+        vessel.update(
+            &mut vec!["propulsion", "port", "transmission", "gear"],
+            &json!("Reverse"),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["propulsion", "port", "fuel", "rate"],
+            &json!(0.0002),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["propulsion", "port", "state"],
+            &json!("started"),
+            &info,
+        );
+        let engine = &vessel.propulsion.as_ref().unwrap()["port"];
+        assert_eq!(
+            engine.transmission.as_ref().unwrap().gear,
+            Some(V1PropulsionGear::new(V1PropulsionGearValue::Reverse))
+        );
+        assert_eq!(
+            engine.state,
+            Some(V1PropulsionState::Value(V1PropulsionStateValue::Started))
+        );
+        assert_eq!(get(&vessel, "propulsion.port.fuel.rate"), Ok(0.0002));
+    }
+
+    #[test]
+    fn propulsion_round_trip_through_deltas() {
+        let full = V1FullFormat::builder()
+            .self_("vessels.urn:mrn:signalk:uuid:c0d79334-4e25-4245-8892-54e8ccc8021d".into())
+            .add_vessel(
+                "urn:mrn:signalk:uuid:c0d79334-4e25-4245-8892-54e8ccc8021d".into(),
+                propulsion_sample(),
+            )
+            .build();
+        let mut copy = V1FullFormat::builder().self_(full.self_.clone()).build();
+        for delta in full.to_deltas() {
+            copy.apply_delta(&delta);
+        }
+        assert_eq!(
+            copy.get_self().unwrap().propulsion,
+            full.get_self().unwrap().propulsion
+        );
+    }
+}
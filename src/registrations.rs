@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::delta::V1UpdateInfo;
+
+/// The various registrations of the vessel.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Registrations {
+    /// The IMO number of the vessel.
+    pub imo: Option<String>,
+    /// The national registration number of the vessel, identified by the registration authority
+    pub national: Option<HashMap<String, V1Registration>>,
+    /// A local or state registration number of the vessel, identified by the registration authority
+    pub local: Option<HashMap<String, V1Registration>>,
+    /// Other registration or permits for the vessel.
+    pub other: Option<HashMap<String, V1Registration>>,
+}
+
+impl V1Registrations {
+    pub fn builder() -> V1RegistrationsBuilder {
+        V1RegistrationsBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() || info.keeps_value() {
+            return;
+        }
+        let registrations = match path[0] {
+            "imo" => {
+                self.imo = value.as_str().map(|s| s.to_string());
+                return;
+            }
+            "national" => &mut self.national,
+            "local" => &mut self.local,
+            "other" => &mut self.other,
+            &_ => {
+                log::warn!(
+                    "V1Registrations: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+                return;
+            }
+        };
+        if path.len() < 2 {
+            *registrations = serde_json::from_value(value.clone()).unwrap_or_default();
+            return;
+        }
+        let registrations = registrations.get_or_insert_with(HashMap::new);
+        let key = path[1].to_string();
+        path.remove(0); // Remove the kind of registration
+        path.remove(0); // and the name of the authority
+        if path.is_empty() {
+            if let Some(registration) = V1Registration::from_value(value) {
+                registrations.insert(key, registration);
+            } else {
+                registrations.remove(&key);
+            }
+        } else {
+            registrations.entry(key).or_default().update(path, value);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1RegistrationsBuilder {
+    imo: Option<String>,
+    national: Option<HashMap<String, V1Registration>>,
+    local: Option<HashMap<String, V1Registration>>,
+    other: Option<HashMap<String, V1Registration>>,
+}
+
+impl V1RegistrationsBuilder {
+    pub fn imo(mut self, value: String) -> V1RegistrationsBuilder {
+        self.imo = Some(value);
+        self
+    }
+    pub fn add_national(mut self, key: String, value: V1Registration) -> V1RegistrationsBuilder {
+        self.national
+            .get_or_insert_with(HashMap::new)
+            .insert(key, value);
+        self
+    }
+    pub fn add_local(mut self, key: String, value: V1Registration) -> V1RegistrationsBuilder {
+        self.local
+            .get_or_insert_with(HashMap::new)
+            .insert(key, value);
+        self
+    }
+    pub fn add_other(mut self, key: String, value: V1Registration) -> V1RegistrationsBuilder {
+        self.other
+            .get_or_insert_with(HashMap::new)
+            .insert(key, value);
+        self
+    }
+    pub fn build(self) -> V1Registrations {
+        V1Registrations {
+            imo: self.imo,
+            national: self.national,
+            local: self.local,
+            other: self.other,
+        }
+    }
+}
+
+/// A registration of the vessel with a registration authority
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Registration {
+    /// The ISO 3166-2 country or state code
+    pub country: Option<String>,
+    /// The registration code
+    pub registration: Option<String>,
+    /// The registration description
+    pub description: Option<String>,
+}
+
+impl V1Registration {
+    pub fn builder() -> V1RegistrationBuilder {
+        V1RegistrationBuilder::default()
+    }
+
+    pub fn from_value(value: &serde_json::value::Value) -> Option<Self> {
+        if value.is_null() {
+            None
+        } else {
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+        match path[0] {
+            "country" => self.country = value.as_str().map(|s| s.to_string()),
+            "registration" => self.registration = value.as_str().map(|s| s.to_string()),
+            "description" => self.description = value.as_str().map(|s| s.to_string()),
+            &_ => {
+                log::warn!(
+                    "V1Registration: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1RegistrationBuilder {
+    country: Option<String>,
+    registration: Option<String>,
+    description: Option<String>,
+}
+
+impl V1RegistrationBuilder {
+    pub fn country(mut self, value: String) -> V1RegistrationBuilder {
+        self.country = Some(value);
+        self
+    }
+    pub fn registration(mut self, value: String) -> V1RegistrationBuilder {
+        self.registration = Some(value);
+        self
+    }
+    pub fn description(mut self, value: String) -> V1RegistrationBuilder {
+        self.description = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Registration {
+        V1Registration {
+            country: self.country,
+            registration: self.registration,
+            description: self.description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod registrations_tests {
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::registrations::{V1Registration, V1Registrations};
+    use crate::{V1FullFormat, V1UpdateInfo, V1Vessel};
+
+    fn registrations_sample() -> V1FullFormat {
+        let file = File::open("tests/specification/test_data/full-valid/registrations-sample.json")
+            .unwrap();
+        serde_json::from_reader(BufReader::new(file)).unwrap()
+    }
+
+    #[test]
+    fn registrations_from_sample() {
+        let full = registrations_sample();
+        let registrations = full.get_self().unwrap().registrations.clone().unwrap();
+        assert_eq!(registrations.imo, Some("IMO 1234567".to_string()));
+        assert_eq!(
+            registrations.national.unwrap()["partA"],
+            V1Registration::builder()
+                .country("NZ".into())
+                .registration("NZ12345".into())
+                .description("New Zealand Part A recreational vessel".into())
+                .build()
+        );
+        assert_eq!(
+            registrations.local.unwrap()["coastGuard"].registration,
+            Some("NZCG12345".to_string())
+        );
+        assert_eq!(
+            full.get_for_path::<String>("self.registrations.other.tassieDoc.registration".into()),
+            Ok("DOC12345".to_string())
+        );
+    }
+
+    #[test]
+    fn registrations_round_trip_through_deltas() {
+        let full = registrations_sample();
+        let mut copy = V1FullFormat::builder().self_(full.self_.clone()).build();
+        for delta in full.to_deltas() {
+            copy.apply_delta(&delta);
+        }
+        assert_eq!(
+            copy.get_self().unwrap().registrations,
+            full.get_self().unwrap().registrations
+        );
+    }
+
+    #[test]
+    fn update_registrations() {
+        let mut vessel = V1Vessel::default();
+        let info = V1UpdateInfo::default();
+        vessel.update(
+            &mut vec!["registrations", "imo"],
+            &json!("IMO 7654321"),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["registrations", "local", "harbour"],
+            &json!({"registration": "H-42", "description": "Harbour permit"}),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["registrations", "national", "se", "country"],
+            &json!("SE"),
+            &info,
+        );
+        assert_eq!(
+            vessel.registrations,
+            Some(
+                V1Registrations::builder()
+                    .imo("IMO 7654321".into())
+                    .add_local(
+                        "harbour".into(),
+                        V1Registration::builder()
+                            .registration("H-42".into())
+                            .description("Harbour permit".into())
+                            .build()
+                    )
+                    .add_national(
+                        "se".into(),
+                        V1Registration::builder().country("SE".into()).build()
+                    )
+                    .build()
+            )
+        );
+    }
+}
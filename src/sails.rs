@@ -0,0 +1,548 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::definitions::{update_value, V1NumberValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, get_f64_value_for_path, F64CompatiblePath};
+use crate::SignalKGetError;
+
+/// Sail data, the sails on board and the sail area in use
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Sails {
+    /// The sails on board, each identified by a unique name i.e. mainsail
+    pub inventory: Option<HashMap<String, V1Sail>>,
+    pub area: Option<V1SailsArea>,
+}
+
+impl V1Sails {
+    pub fn builder() -> V1SailsBuilder {
+        V1SailsBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "inventory" if path.len() > 1 => {
+                let inventory = self.inventory.get_or_insert_with(HashMap::new);
+                let key = path[1].to_string();
+                path.remove(0); // Remove inventory
+                path.remove(0); // and the name of the sail
+                if path.is_empty() {
+                    if info.keeps_value() {
+                        return;
+                    }
+                    if let Some(sail) = V1Sail::from_value(value) {
+                        inventory.insert(key, sail);
+                    } else {
+                        inventory.remove(&key);
+                    }
+                } else {
+                    inventory.entry(key).or_default().update(path, value, info);
+                }
+            }
+            "area" => {
+                if self.area.is_none() {
+                    self.area = Some(V1SailsArea::default());
+                }
+                if let Some(ref mut area) = self.area {
+                    path.remove(0);
+                    area.update(path, value, info);
+                }
+            }
+            &_ => {
+                log::warn!("V1Sails: Unknown update pattern: {:?}::{:?}", path, value);
+            }
+        }
+    }
+}
+
+impl F64CompatiblePath for V1Sails {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        match path[0] {
+            "inventory" => {
+                if path.len() < 3 {
+                    return Err(SignalKGetError::WrongDataType);
+                }
+                if let Some(ref inventory) = self.inventory {
+                    if let Some(sail) = inventory.get(path[1]) {
+                        path.remove(0); // Remove inventory
+                        path.remove(0); // and the name of the sail
+                        sail.get_f64_for_path(path)
+                    } else {
+                        Err(SignalKGetError::NoSuchPath)
+                    }
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "area" => get_f64_value_for_path(path, &self.area),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SailsBuilder {
+    inventory: Option<HashMap<String, V1Sail>>,
+    area: Option<V1SailsArea>,
+}
+
+impl V1SailsBuilder {
+    pub fn add_sail(mut self, key: String, value: V1Sail) -> V1SailsBuilder {
+        self.inventory
+            .get_or_insert_with(HashMap::new)
+            .insert(key, value);
+        self
+    }
+    pub fn area(mut self, value: V1SailsArea) -> V1SailsBuilder {
+        self.area = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Sails {
+        V1Sails {
+            inventory: self.inventory,
+            area: self.area,
+        }
+    }
+}
+
+/// A sail in the inventory of the vessel
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Sail {
+    /// An unique identifier by which the crew identifies a sail
+    pub name: Option<String>,
+    /// The type of sail
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// The material the sail is made from (optional)
+    pub material: Option<String>,
+    /// The brand of the sail (optional)
+    pub brand: Option<String>,
+    /// Indicates wether this sail is currently in use or not
+    pub active: Option<bool>,
+    /// The total area of this sail in square meters
+    pub area: Option<f64>,
+    /// The minimum wind speed this sail can be used with
+    pub minimum_wind: Option<f64>,
+    /// The maximum wind speed this sail can be used with
+    pub maximum_wind: Option<f64>,
+    /// An object describing reduction of sail area
+    pub reduced_state: Option<V1SailReducedState>,
+}
+
+impl V1Sail {
+    pub fn builder() -> V1SailBuilder {
+        V1SailBuilder::default()
+    }
+
+    pub fn from_value(value: &serde_json::value::Value) -> Option<Self> {
+        if value.is_null() {
+            None
+        } else {
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() || info.keeps_value() {
+            return;
+        }
+        match path[0] {
+            "name" => self.name = value.as_str().map(|s| s.to_string()),
+            "type" => self.type_ = value.as_str().map(|s| s.to_string()),
+            "material" => self.material = value.as_str().map(|s| s.to_string()),
+            "brand" => self.brand = value.as_str().map(|s| s.to_string()),
+            "active" => self.active = value.as_bool(),
+            "area" => self.area = value.as_f64(),
+            "minimumWind" => self.minimum_wind = value.as_f64(),
+            "maximumWind" => self.maximum_wind = value.as_f64(),
+            "reducedState" => {
+                path.remove(0);
+                if path.is_empty() {
+                    self.reduced_state = V1SailReducedState::from_value(value);
+                } else {
+                    self.reduced_state
+                        .get_or_insert_with(V1SailReducedState::default)
+                        .update(path, value);
+                }
+            }
+            &_ => {
+                log::warn!("V1Sail: Unknown update pattern: {:?}::{:?}", path, value);
+            }
+        }
+    }
+}
+
+impl F64CompatiblePath for V1Sail {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "name" => Err(SignalKGetError::WrongDataType),
+            "type" => Err(SignalKGetError::WrongDataType),
+            "material" => Err(SignalKGetError::WrongDataType),
+            "brand" => Err(SignalKGetError::WrongDataType),
+            "active" => Err(SignalKGetError::WrongDataType),
+            "area" => self.area.ok_or(SignalKGetError::ValueNotSet),
+            "minimumWind" => self.minimum_wind.ok_or(SignalKGetError::ValueNotSet),
+            "maximumWind" => self.maximum_wind.ok_or(SignalKGetError::ValueNotSet),
+            "reducedState" => {
+                if path.len() < 2 {
+                    return Err(SignalKGetError::WrongDataType);
+                }
+                get_f64_value_for_path(path, &self.reduced_state)
+            }
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SailBuilder {
+    name: Option<String>,
+    type_: Option<String>,
+    material: Option<String>,
+    brand: Option<String>,
+    active: Option<bool>,
+    area: Option<f64>,
+    minimum_wind: Option<f64>,
+    maximum_wind: Option<f64>,
+    reduced_state: Option<V1SailReducedState>,
+}
+
+impl V1SailBuilder {
+    pub fn name(mut self, value: String) -> V1SailBuilder {
+        self.name = Some(value);
+        self
+    }
+    pub fn type_(mut self, value: String) -> V1SailBuilder {
+        self.type_ = Some(value);
+        self
+    }
+    pub fn material(mut self, value: String) -> V1SailBuilder {
+        self.material = Some(value);
+        self
+    }
+    pub fn brand(mut self, value: String) -> V1SailBuilder {
+        self.brand = Some(value);
+        self
+    }
+    pub fn active(mut self, value: bool) -> V1SailBuilder {
+        self.active = Some(value);
+        self
+    }
+    pub fn area(mut self, value: f64) -> V1SailBuilder {
+        self.area = Some(value);
+        self
+    }
+    pub fn minimum_wind(mut self, value: f64) -> V1SailBuilder {
+        self.minimum_wind = Some(value);
+        self
+    }
+    pub fn maximum_wind(mut self, value: f64) -> V1SailBuilder {
+        self.maximum_wind = Some(value);
+        self
+    }
+    pub fn reduced_state(mut self, value: V1SailReducedState) -> V1SailBuilder {
+        self.reduced_state = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Sail {
+        V1Sail {
+            name: self.name,
+            type_: self.type_,
+            material: self.material,
+            brand: self.brand,
+            active: self.active,
+            area: self.area,
+            minimum_wind: self.minimum_wind,
+            maximum_wind: self.maximum_wind,
+            reduced_state: self.reduced_state,
+        }
+    }
+}
+
+/// How much a sail is reefed or furled
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1SailReducedState {
+    /// describes whether the sail is reduced or not
+    pub reduced: Option<bool>,
+    /// Number of reefs set, 0 means full
+    pub reefs: Option<f64>,
+    /// Ratio of sail reduction, 0 means full and 1 is completely furled in
+    pub furled_ratio: Option<f64>,
+}
+
+impl V1SailReducedState {
+    pub fn builder() -> V1SailReducedStateBuilder {
+        V1SailReducedStateBuilder::default()
+    }
+
+    pub fn from_value(value: &serde_json::value::Value) -> Option<Self> {
+        if value.is_null() {
+            None
+        } else {
+            serde_json::from_value(value.clone()).ok()
+        }
+    }
+
+    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+        match path[0] {
+            "reduced" => self.reduced = value.as_bool(),
+            "reefs" => self.reefs = value.as_f64(),
+            "furledRatio" => self.furled_ratio = value.as_f64(),
+            &_ => {
+                log::warn!(
+                    "V1SailReducedState: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl F64CompatiblePath for V1SailReducedState {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "reduced" => Err(SignalKGetError::WrongDataType),
+            "reefs" => self.reefs.ok_or(SignalKGetError::ValueNotSet),
+            "furledRatio" => self.furled_ratio.ok_or(SignalKGetError::ValueNotSet),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SailReducedStateBuilder {
+    reduced: Option<bool>,
+    reefs: Option<f64>,
+    furled_ratio: Option<f64>,
+}
+
+impl V1SailReducedStateBuilder {
+    pub fn reduced(mut self, value: bool) -> V1SailReducedStateBuilder {
+        self.reduced = Some(value);
+        self
+    }
+    pub fn reefs(mut self, value: f64) -> V1SailReducedStateBuilder {
+        self.reefs = Some(value);
+        self
+    }
+    pub fn furled_ratio(mut self, value: f64) -> V1SailReducedStateBuilder {
+        self.furled_ratio = Some(value);
+        self
+    }
+    pub fn build(self) -> V1SailReducedState {
+        V1SailReducedState {
+            reduced: self.reduced,
+            reefs: self.reefs,
+            furled_ratio: self.furled_ratio,
+        }
+    }
+}
+
+/// The sail area of the vessel
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1SailsArea {
+    /// The total area of all sails on the vessel
+    pub total: Option<V1NumberValue>,
+    /// The total area of the sails currently in use on the vessel
+    pub active: Option<V1NumberValue>,
+}
+
+impl V1SailsArea {
+    pub fn builder() -> V1SailsAreaBuilder {
+        V1SailsAreaBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "total" => update_value(&mut self.total, value, info),
+            "active" => update_value(&mut self.active, value, info),
+            &_ => {
+                log::warn!(
+                    "V1SailsArea: Unknown update pattern: {:?}::{:?}",
+                    path,
+                    value
+                );
+            }
+        }
+    }
+}
+
+impl F64CompatiblePath for V1SailsArea {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.is_empty() {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        match path[0] {
+            "total" => get_f64_value(&self.total),
+            "active" => get_f64_value(&self.active),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SailsAreaBuilder {
+    total: Option<V1NumberValue>,
+    active: Option<V1NumberValue>,
+}
+
+impl V1SailsAreaBuilder {
+    pub fn total(mut self, value: V1NumberValue) -> V1SailsAreaBuilder {
+        self.total = Some(value);
+        self
+    }
+    pub fn active(mut self, value: V1NumberValue) -> V1SailsAreaBuilder {
+        self.active = Some(value);
+        self
+    }
+    pub fn build(self) -> V1SailsArea {
+        V1SailsArea {
+            total: self.total,
+            active: self.active,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sails_tests {
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::sails::{V1Sail, V1SailReducedState, V1Sails};
+    use crate::{SignalKGetError, V1UpdateInfo, V1Vessel};
+
+    #[test]
+    fn sails_sample() {
+        let file =
+            File::open("tests/specification/test_data/vessel-valid/sails-sample.json").unwrap();
+        let vessel: V1Vessel = serde_json::from_reader(BufReader::new(file)).unwrap();
+        let sails = vessel.sails.as_ref().unwrap();
+        let genoa = &sails.inventory.as_ref().unwrap()["genoa"];
+        assert_eq!(genoa.name, Some("Cruising genoa".to_string()));
+        assert_eq!(genoa.active, Some(true));
+        assert_eq!(
+            genoa.reduced_state,
+            Some(
+                V1SailReducedState::builder()
+                    .reduced(true)
+                    .reefs(1.0)
+                    .furled_ratio(0.0)
+                    .build()
+            )
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sails", "area", "active"]),
+            Ok(78.4)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sails", "inventory", "racingMain", "area"]),
+            Ok(34.0)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sails", "inventory", "genoa", "active"]),
+            Err(SignalKGetError::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn update_sails() {
+        let mut vessel = V1Vessel::default();
+        let info = V1UpdateInfo::default();
+        vessel.update(&mut vec!["sails", "area", "total"], &json!(82.0), &info);
+        vessel.update(
+            &mut vec!["sails", "inventory", "main"],
+            &json!({"name": "Main", "type": "main", "area": 33.8, "active": true}),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["sails", "inventory", "main", "reducedState", "reefs"],
+            &json!(2),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["sails", "inventory", "jib", "active"],
+            &json!(false),
+            &info,
+        );
+        let sails = vessel.sails.as_ref().unwrap();
+        assert_eq!(
+            sails.inventory.as_ref().unwrap()["main"],
+            V1Sail::builder()
+                .name("Main".into())
+                .type_("main".into())
+                .area(33.8)
+                .active(true)
+                .reduced_state(V1SailReducedState::builder().reefs(2.0).build())
+                .build()
+        );
+        assert_eq!(
+            sails.inventory.as_ref().unwrap()["jib"],
+            V1Sail::builder().active(false).build()
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sails", "area", "total"]),
+            Ok(82.0)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec![
+                "sails",
+                "inventory",
+                "main",
+                "reducedState",
+                "reefs"
+            ]),
+            Ok(2.0)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sails", "inventory", "spinnaker", "area"]),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn sails_round_trip() {
+        let sails = V1Sails::builder()
+            .add_sail(
+                "genoa".into(),
+                V1Sail::builder()
+                    .name("Cruising genoa".into())
+                    .area(44.6)
+                    .build(),
+            )
+            .build();
+        let json = serde_json::to_value(&sails).unwrap();
+        assert_eq!(json["inventory"]["genoa"]["area"], json!(44.6));
+        let parsed: V1Sails = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, sails);
+    }
+}
@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::definitions::{update_value, V1NumberValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, F64CompatiblePath};
+use crate::SignalKGetError;
+
+/// Sensors, their state, and data, each identified by a unique name i.e. gps
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct V1Sensors {
+    #[serde(flatten)]
+    pub sensors: HashMap<String, V1Sensor>,
+}
+
+impl V1Sensors {
+    pub fn builder() -> V1SensorsBuilder {
+        V1SensorsBuilder::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V1Sensor> {
+        self.sensors.get(key)
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.len() < 2 {
+            log::warn!("V1Sensors: Unknown update pattern: {:?}::{:?}", path, value);
+            return;
+        }
+        let sensor = self.sensors.entry(path[0].to_string()).or_default();
+        path.remove(0);
+        sensor.update(path, value, info);
+    }
+}
+
+impl F64CompatiblePath for V1Sensors {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.len() < 2 {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        if let Some(sensor) = self.sensors.get(path[0]) {
+            path.remove(0);
+            sensor.get_f64_for_path(path)
+        } else {
+            Err(SignalKGetError::NoSuchPath)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SensorsBuilder {
+    sensors: HashMap<String, V1Sensor>,
+}
+
+impl V1SensorsBuilder {
+    pub fn add_sensor(mut self, key: String, value: V1Sensor) -> V1SensorsBuilder {
+        self.sensors.insert(key, value);
+        self
+    }
+    pub fn build(self) -> V1Sensors {
+        V1Sensors {
+            sensors: self.sensors,
+        }
+    }
+}
+
+/// An object describing an individual sensor, and where it is on the vessel
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Sensor {
+    /// The common name of the sensor
+    pub name: Option<String>,
+    /// The datamodel definition of the sensor data
+    pub sensor_type: Option<String>,
+    /// The data of the sensor, as defined by the sensor type
+    pub sensor_data: Option<serde_json::Value>,
+    /// The distance from the bow to the sensor location
+    pub from_bow: Option<V1NumberValue>,
+    /// The distance from the centerline to the sensor location, -ve to starboard, +ve to port
+    pub from_center: Option<V1NumberValue>,
+}
+
+impl V1Sensor {
+    pub fn builder() -> V1SensorBuilder {
+        V1SensorBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        // Only the number values keep the values of other sources
+        let replace = !info.keeps_value();
+        match path[0] {
+            "name" if replace => self.name = value.as_str().map(|s| s.to_string()),
+            "sensorType" if replace => self.sensor_type = value.as_str().map(|s| s.to_string()),
+            "sensorData" if replace => {
+                path.remove(0);
+                self.update_sensor_data(path, value);
+            }
+            "name" | "sensorType" | "sensorData" => {}
+            "fromBow" => update_value(&mut self.from_bow, value, info),
+            "fromCenter" => update_value(&mut self.from_center, value, info),
+            &_ => {
+                log::warn!("V1Sensor: Unknown update pattern: {:?}::{:?}", path, value);
+            }
+        }
+    }
+
+    fn update_sensor_data(&mut self, path: &[&str], value: &serde_json::value::Value) {
+        let Some((key, parents)) = path.split_last() else {
+            self.sensor_data = if value.is_null() {
+                None
+            } else {
+                Some(value.clone())
+            };
+            return;
+        };
+        let mut data = self
+            .sensor_data
+            .get_or_insert_with(|| serde_json::Value::Object(Default::default()));
+        for parent in parents {
+            if !data.is_object() {
+                *data = serde_json::Value::Object(Default::default());
+            }
+            data = data
+                .as_object_mut()
+                .unwrap()
+                .entry(parent.to_string())
+                .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        }
+        if !data.is_object() {
+            *data = serde_json::Value::Object(Default::default());
+        }
+        let object = data.as_object_mut().unwrap();
+        if value.is_null() {
+            object.remove(*key);
+        } else {
+            object.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+impl F64CompatiblePath for V1Sensor {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "name" => Err(SignalKGetError::WrongDataType),
+            "sensorType" => Err(SignalKGetError::WrongDataType),
+            "sensorData" => {
+                let Some(ref data) = self.sensor_data else {
+                    return Err(SignalKGetError::ValueNotSet);
+                };
+                let mut data = data;
+                for key in path[1..].iter() {
+                    data = data.get(key).ok_or(SignalKGetError::NoSuchPath)?;
+                }
+                data.as_f64().ok_or(SignalKGetError::WrongDataType)
+            }
+            "fromBow" => get_f64_value(&self.from_bow),
+            "fromCenter" => get_f64_value(&self.from_center),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1SensorBuilder {
+    name: Option<String>,
+    sensor_type: Option<String>,
+    sensor_data: Option<serde_json::Value>,
+    from_bow: Option<V1NumberValue>,
+    from_center: Option<V1NumberValue>,
+}
+
+impl V1SensorBuilder {
+    pub fn name(mut self, value: String) -> V1SensorBuilder {
+        self.name = Some(value);
+        self
+    }
+    pub fn sensor_type(mut self, value: String) -> V1SensorBuilder {
+        self.sensor_type = Some(value);
+        self
+    }
+    pub fn sensor_data(mut self, value: serde_json::Value) -> V1SensorBuilder {
+        self.sensor_data = Some(value);
+        self
+    }
+    pub fn from_bow(mut self, value: V1NumberValue) -> V1SensorBuilder {
+        self.from_bow = Some(value);
+        self
+    }
+    pub fn from_center(mut self, value: V1NumberValue) -> V1SensorBuilder {
+        self.from_center = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Sensor {
+        V1Sensor {
+            name: self.name,
+            sensor_type: self.sensor_type,
+            sensor_data: self.sensor_data,
+            from_bow: self.from_bow,
+            from_center: self.from_center,
+        }
+    }
+}
+
+#[cfg(test)]
+mod sensors_tests {
+    use serde_json::json;
+
+    use crate::sensors::{V1Sensor, V1Sensors};
+    use crate::{SignalKGetError, V1NumberValue, V1UpdateInfo, V1Vessel};
+
+    #[test]
+    fn update_sensors() {
+        let mut vessel = V1Vessel::default();
+        let info = V1UpdateInfo::default();
+        vessel.update(&mut vec!["sensors", "gps", "fromBow"], &json!(3.5), &info);
+        vessel.update(
+            &mut vec!["sensors", "gps", "sensorType"],
+            &json!("gnss"),
+            &info,
+        );
+        vessel.update(
+            &mut vec!["sensors", "gps", "sensorData", "satellites"],
+            &json!(9),
+            &info,
+        );
+        let gps = vessel.sensors.as_ref().unwrap().get("gps").unwrap();
+        assert_eq!(gps.sensor_type, Some("gnss".to_string()));
+        assert_eq!(gps.sensor_data, Some(json!({"satellites": 9})));
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sensors", "gps", "fromBow"]),
+            Ok(3.5)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sensors", "gps", "sensorData", "satellites"]),
+            Ok(9.0)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sensors", "gps", "fromCenter"]),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sensors", "gps", "sensorType"]),
+            Err(SignalKGetError::WrongDataType)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["sensors", "radar", "fromBow"]),
+            Err(SignalKGetError::NoSuchPath)
+        );
+    }
+
+    #[test]
+    fn sensors_round_trip() {
+        let sensors = V1Sensors::builder()
+            .add_sensor(
+                "gps".into(),
+                V1Sensor::builder()
+                    .name("Masthead GPS".into())
+                    .sensor_type("gnss".into())
+                    .from_center(V1NumberValue::builder().value(-0.2).build())
+                    .build(),
+            )
+            .build();
+        let json = serde_json::to_value(&sensors).unwrap();
+        assert_eq!(json["gps"]["sensorType"], json!("gnss"));
+        let parsed: V1Sensors = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, sensors);
+    }
+}
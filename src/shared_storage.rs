@@ -0,0 +1,272 @@
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use time::OffsetDateTime;
+
+use crate::{
+    SignalKGetError, Storage, V1DeltaFormat, V1FullFormat, V1HistoryLimit, V1HistorySample,
+    V1HistoryStatistics, V1Meta, V1NotificationValue, V1PathChange, V1SourcePriority,
+    V1SourceValue,
+};
+
+/// A handle to a `Storage` that can be shared between threads
+///
+/// Cloning the handle is cheap, all clones use the same storage. Any number
+/// of threads can read at the same time, updates wait for the readers to
+/// finish. The path getters only copy the group of the vessel that holds the
+/// path, use `read` to look at the data without copying it.
+///
+/// # Examples
+/// ```
+/// use signalk::{SharedStorage, V1DeltaFormat};
+/// use std::thread;
+/// let storage = SharedStorage::default();
+/// storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+/// let writer = storage.clone();
+/// thread::spawn(move || {
+///     let j = r#"{"updates": [
+///                    {"values": [{"path": "navigation.courseOverGroundTrue", "value": 123.45 }]}
+///                 ],
+///                 "context": "vessels.urn:mrn:imo:mmsi:366982330"
+///     }"#;
+///     let delta_message: V1DeltaFormat = serde_json::from_str(j).unwrap();
+///     writer.update(&delta_message);
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(
+///     storage.get_f64_for_path("self.navigation.courseOverGroundTrue".to_string()),
+///     Ok(123.45)
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SharedStorage {
+    storage: Arc<RwLock<Storage>>,
+}
+
+impl SharedStorage {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            storage: Arc::new(RwLock::new(storage)),
+        }
+    }
+
+    /// Lock the storage for reading
+    ///
+    /// Updates wait until the guard is dropped, keep it short lived.
+    pub fn read(&self) -> RwLockReadGuard<'_, Storage> {
+        self.storage.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the storage for writing
+    pub fn write(&self) -> RwLockWriteGuard<'_, Storage> {
+        self.storage.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set the id of the self vessel.
+    pub fn set_self(&self, value: &str) {
+        self.write().set_self(value);
+    }
+
+    /// Apply a Delta change to the structure
+    pub fn update(&self, delta: &V1DeltaFormat) {
+        self.write().update(delta);
+    }
+
+    /// Set the sources allowed to provide the current value of a path
+    pub fn set_source_priorities(&self, path: &str, priorities: Vec<V1SourcePriority>) {
+        self.write().set_source_priorities(path, priorities);
+    }
+
+    /// Call `callback` when an update changes a leaf matching the patterns
+    ///
+    /// The callback runs while the storage is locked for writing, it must not
+    /// use the storage itself.
+    pub fn on_change<F>(&self, context: &str, path: &str, callback: F)
+    where
+        F: FnMut(&V1PathChange) + Send + Sync + 'static,
+    {
+        self.write().on_change(context, path, callback);
+    }
+
+    /// Get a channel receiving changes of leaves matching the patterns
+    pub fn subscribe(&self, context: &str, path: &str) -> Receiver<V1PathChange> {
+        self.write().subscribe(context, path)
+    }
+
+    /// Get a clone of the stored data
+    pub fn get(&self) -> V1FullFormat {
+        self.read().get()
+    }
+
+    pub fn get_f64_for_path(&self, path: String) -> Result<f64, SignalKGetError> {
+        self.read().get_f64_for_path(path)
+    }
+
+    pub fn get_for_path<T: DeserializeOwned + PartialEq>(
+        &self,
+        path: String,
+    ) -> Result<T, SignalKGetError> {
+        self.read().get_for_path(path)
+    }
+
+    pub fn get_json_for_path(&self, path: &str) -> Result<serde_json::Value, SignalKGetError> {
+        self.read().get_json_for_path(path)
+    }
+
+    pub fn get_meta_for_path(&self, path: &str) -> Result<V1Meta, SignalKGetError> {
+        self.read().get_meta_for_path(path)
+    }
+
+    pub fn get_values_for_path(
+        &self,
+        path: &str,
+    ) -> Result<HashMap<String, V1SourceValue>, SignalKGetError> {
+        self.read().get_values_for_path(path)
+    }
+
+    pub fn get_preferred_value_for_path(
+        &self,
+        path: &str,
+        preferred: &[&str],
+    ) -> Result<(String, V1SourceValue), SignalKGetError> {
+        self.read().get_preferred_value_for_path(path, preferred)
+    }
+
+    pub fn get_notifications(
+        &self,
+        context: &str,
+        state: Option<&str>,
+    ) -> Result<Vec<(String, V1NotificationValue)>, SignalKGetError> {
+        self.read().get_notifications(context, state)
+    }
+
+    pub fn query(&self, context: &str, path: &str) -> Vec<(String, String, serde_json::Value)> {
+        self.read().query(context, path)
+    }
+
+    /// Start recording the numeric values of paths matching a pattern
+    pub fn enable_history(&self, path: &str, limit: V1HistoryLimit) {
+        self.write().enable_history(path, limit);
+    }
+
+    /// Stop recording paths matching a pattern and forget their samples
+    pub fn disable_history(&self, path: &str) {
+        self.write().disable_history(path);
+    }
+
+    pub fn get_history(
+        &self,
+        path: &str,
+        since: Option<OffsetDateTime>,
+    ) -> Result<Vec<V1HistorySample>, SignalKGetError> {
+        self.read().get_history(path, since)
+    }
+
+    pub fn get_history_statistics(
+        &self,
+        path: &str,
+        since: Option<OffsetDateTime>,
+    ) -> Result<V1HistoryStatistics, SignalKGetError> {
+        self.read().get_history_statistics(path, since)
+    }
+
+    pub fn is_stale(&self, path: &str, now: OffsetDateTime) -> Result<bool, SignalKGetError> {
+        self.read().is_stale(path, now)
+    }
+
+    /// List the full paths of all values that are stale at `now`
+    pub fn stale_paths(&self, now: OffsetDateTime) -> Vec<String> {
+        self.read().stale_paths(now)
+    }
+
+    /// Clear all values that are stale at `now` and return their full paths
+    pub fn expire_stale_paths(&self, now: OffsetDateTime) -> Vec<String> {
+        self.write().expire_stale_paths(now)
+    }
+}
+
+impl From<Storage> for SharedStorage {
+    fn from(storage: Storage) -> Self {
+        Self::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod shared_storage_tests {
+    use serde_json::{Number, Value};
+    use std::thread;
+
+    use crate::{SharedStorage, V1DeltaFormat, V1UpdateType, V1UpdateValue};
+
+    fn voltage_delta(voltage: f64) -> V1DeltaFormat {
+        V1DeltaFormat::builder()
+            .context("vessels.urn:mrn:imo:mmsi:366982330".into())
+            .add_update(
+                V1UpdateType::builder()
+                    .add_update(V1UpdateValue::new(
+                        "electrical.batteries.house.voltage".into(),
+                        Value::Number(Number::from_f64(voltage).unwrap()),
+                    ))
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn shared_storage_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedStorage>();
+    }
+
+    #[test]
+    fn clones_share_the_same_storage() {
+        let storage = SharedStorage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        let other = storage.clone();
+        other.update(&voltage_delta(12.6));
+        assert_eq!(
+            storage.get_f64_for_path("self.electrical.batteries.house.voltage".into()),
+            Ok(12.6)
+        );
+    }
+
+    #[test]
+    fn readers_and_writer_in_parallel() {
+        let storage = SharedStorage::default();
+        storage.set_self("vessels.urn:mrn:imo:mmsi:366982330");
+        storage.update(&voltage_delta(12.0));
+        let changes = storage.subscribe("self", "electrical.batteries.*.voltage");
+        let writer = {
+            let storage = storage.clone();
+            thread::spawn(move || {
+                for i in 1..=100 {
+                    storage.update(&voltage_delta(12.0 + i as f64 / 100.0));
+                }
+            })
+        };
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let storage = storage.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let voltage = storage
+                            .get_f64_for_path("self.electrical.batteries.house.voltage".into())
+                            .unwrap();
+                        assert!((12.0..=13.0).contains(&voltage));
+                    }
+                })
+            })
+            .collect();
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(changes.try_iter().count(), 100);
+        assert_eq!(
+            storage.get_f64_for_path("self.electrical.batteries.house.voltage".into()),
+            Ok(13.0)
+        );
+    }
+}
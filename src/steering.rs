@@ -40,6 +40,16 @@ pub struct V1SteeringAutopilot {
 impl F64CompatiblePath for V1SteeringAutopilot {
     fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
         match path[0] {
+            "state" => Err(SignalKGetError::WrongDataType),
+            "mode" => Err(SignalKGetError::WrongDataType),
+            "target" => get_f64_value_for_path(path, &self.target),
+            "deadZone" => get_f64_value(&self.dead_zone),
+            "backlash" => get_f64_value(&self.backlash),
+            "gain" => get_f64_value(&self.gain),
+            "maxDriveCurrent" => get_f64_value(&self.max_drive_current),
+            "maxDriveRate" => get_f64_value(&self.max_drive_rate),
+            "portLock" => get_f64_value(&self.port_lock),
+            "starboardLock" => get_f64_value(&self.starboard_lock),
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
@@ -0,0 +1,395 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::definitions::{update_value, V1NumberValue, V1StringValue};
+use crate::delta::V1UpdateInfo;
+use crate::helper_functions::{get_f64_value, F64CompatiblePath};
+use crate::SignalKGetError;
+
+/// Tank data, each tank identified by a unique name i.e. FreshWater_2
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Tanks {
+    pub fresh_water: Option<HashMap<String, V1Tank>>,
+    pub waste_water: Option<HashMap<String, V1Tank>>,
+    pub black_water: Option<HashMap<String, V1Tank>>,
+    pub fuel: Option<HashMap<String, V1Tank>>,
+    pub lubrication: Option<HashMap<String, V1Tank>>,
+    pub live_well: Option<HashMap<String, V1Tank>>,
+    pub bait_well: Option<HashMap<String, V1Tank>>,
+    pub gas: Option<HashMap<String, V1Tank>>,
+    pub ballast: Option<HashMap<String, V1Tank>>,
+}
+
+impl V1Tanks {
+    pub fn builder() -> V1TanksBuilder {
+        V1TanksBuilder::default()
+    }
+
+    /// The tanks of a type as named in a path, e.g. `freshWater`
+    pub fn tanks(&self, tank_type: &str) -> Option<&Option<HashMap<String, V1Tank>>> {
+        match tank_type {
+            "freshWater" => Some(&self.fresh_water),
+            "wasteWater" => Some(&self.waste_water),
+            "blackWater" => Some(&self.black_water),
+            "fuel" => Some(&self.fuel),
+            "lubrication" => Some(&self.lubrication),
+            "liveWell" => Some(&self.live_well),
+            "baitWell" => Some(&self.bait_well),
+            "gas" => Some(&self.gas),
+            "ballast" => Some(&self.ballast),
+            &_ => None,
+        }
+    }
+
+    fn tanks_mut(&mut self, tank_type: &str) -> Option<&mut Option<HashMap<String, V1Tank>>> {
+        match tank_type {
+            "freshWater" => Some(&mut self.fresh_water),
+            "wasteWater" => Some(&mut self.waste_water),
+            "blackWater" => Some(&mut self.black_water),
+            "fuel" => Some(&mut self.fuel),
+            "lubrication" => Some(&mut self.lubrication),
+            "liveWell" => Some(&mut self.live_well),
+            "baitWell" => Some(&mut self.bait_well),
+            "gas" => Some(&mut self.gas),
+            "ballast" => Some(&mut self.ballast),
+            &_ => None,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.len() < 2 {
+            log::warn!("V1Tanks: Unknown update pattern: {:?}::{:?}", path, value);
+            return;
+        }
+        if let Some(tanks) = self.tanks_mut(path[0]) {
+            let tank = tanks
+                .get_or_insert_with(HashMap::new)
+                .entry(path[1].to_string())
+                .or_default();
+            path.remove(0); // Remove the tank type
+            path.remove(0); // and the name of the tank
+            if path.is_empty() {
+                path.push("");
+            }
+            tank.update(path, value, info);
+        } else {
+            log::warn!("V1Tanks: Unknown update pattern: {:?}::{:?}", path, value);
+        }
+    }
+}
+
+impl F64CompatiblePath for V1Tanks {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        if path.len() < 3 {
+            return Err(SignalKGetError::NoSuchPath);
+        }
+        match self.tanks(path[0]) {
+            Some(Some(tanks)) => {
+                if let Some(tank) = tanks.get(path[1]) {
+                    path.remove(0); // Remove the tank type
+                    path.remove(0); // and the name of the tank
+                    tank.get_f64_for_path(path)
+                } else {
+                    Err(SignalKGetError::NoSuchPath)
+                }
+            }
+            Some(None) => Err(SignalKGetError::ValueNotSet),
+            None => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1TanksBuilder {
+    tanks: V1Tanks,
+}
+
+impl V1TanksBuilder {
+    fn add(mut self, tank_type: &str, key: String, value: V1Tank) -> V1TanksBuilder {
+        if let Some(tanks) = self.tanks.tanks_mut(tank_type) {
+            tanks.get_or_insert_with(HashMap::new).insert(key, value);
+        }
+        self
+    }
+    pub fn add_fresh_water(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("freshWater", key, value)
+    }
+    pub fn add_waste_water(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("wasteWater", key, value)
+    }
+    pub fn add_black_water(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("blackWater", key, value)
+    }
+    pub fn add_fuel(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("fuel", key, value)
+    }
+    pub fn add_lubrication(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("lubrication", key, value)
+    }
+    pub fn add_live_well(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("liveWell", key, value)
+    }
+    pub fn add_bait_well(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("baitWell", key, value)
+    }
+    pub fn add_gas(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("gas", key, value)
+    }
+    pub fn add_ballast(self, key: String, value: V1Tank) -> V1TanksBuilder {
+        self.add("ballast", key, value)
+    }
+    pub fn build(self) -> V1Tanks {
+        self.tanks
+    }
+}
+
+/// A single tank with its level and volume
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct V1Tank {
+    pub name: Option<V1StringValue>,
+    #[serde(rename = "type")]
+    pub type_: Option<V1StringValue>,
+    pub capacity: Option<V1NumberValue>,
+    pub current_level: Option<V1NumberValue>,
+    pub current_volume: Option<V1NumberValue>,
+    pub pressure: Option<V1NumberValue>,
+    pub temperature: Option<V1NumberValue>,
+    pub viscosity: Option<V1NumberValue>,
+    pub extinguishant: Option<V1StringValue>,
+}
+
+impl V1Tank {
+    pub fn builder() -> V1TankBuilder {
+        V1TankBuilder::default()
+    }
+
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
+        if path.is_empty() {
+            return;
+        }
+        match path[0] {
+            "name" => update_value(&mut self.name, value, info),
+            "type" => update_value(&mut self.type_, value, info),
+            "capacity" => update_value(&mut self.capacity, value, info),
+            "currentLevel" => update_value(&mut self.current_level, value, info),
+            "currentVolume" => update_value(&mut self.current_volume, value, info),
+            "pressure" => update_value(&mut self.pressure, value, info),
+            "temperature" => update_value(&mut self.temperature, value, info),
+            "viscosity" => update_value(&mut self.viscosity, value, info),
+            "extinguishant" => update_value(&mut self.extinguishant, value, info),
+            "" => {
+                if let serde_json::Value::Object(ref map) = value {
+                    for (k, v) in map.iter() {
+                        let mut path = vec![k.as_str()];
+                        self.update(&mut path, v, info);
+                    }
+                }
+            }
+            &_ => {
+                log::warn!("V1Tank: Unknown update pattern: {:?}::{:?}", path, value);
+            }
+        }
+    }
+}
+
+impl F64CompatiblePath for V1Tank {
+    fn get_f64_for_path(&self, path: &mut Vec<&str>) -> Result<f64, SignalKGetError> {
+        match path[0] {
+            "name" => Err(SignalKGetError::WrongDataType),
+            "type" => Err(SignalKGetError::WrongDataType),
+            "capacity" => get_f64_value(&self.capacity),
+            "currentLevel" => get_f64_value(&self.current_level),
+            "currentVolume" => get_f64_value(&self.current_volume),
+            "pressure" => get_f64_value(&self.pressure),
+            "temperature" => get_f64_value(&self.temperature),
+            "viscosity" => get_f64_value(&self.viscosity),
+            "extinguishant" => Err(SignalKGetError::WrongDataType),
+            &_ => Err(SignalKGetError::NoSuchPath),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct V1TankBuilder {
+    name: Option<V1StringValue>,
+    type_: Option<V1StringValue>,
+    capacity: Option<V1NumberValue>,
+    current_level: Option<V1NumberValue>,
+    current_volume: Option<V1NumberValue>,
+    pressure: Option<V1NumberValue>,
+    temperature: Option<V1NumberValue>,
+    viscosity: Option<V1NumberValue>,
+    extinguishant: Option<V1StringValue>,
+}
+
+impl V1TankBuilder {
+    pub fn name(mut self, value: V1StringValue) -> V1TankBuilder {
+        self.name = Some(value);
+        self
+    }
+    pub fn type_(mut self, value: V1StringValue) -> V1TankBuilder {
+        self.type_ = Some(value);
+        self
+    }
+    pub fn capacity(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.capacity = Some(value);
+        self
+    }
+    pub fn current_level(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.current_level = Some(value);
+        self
+    }
+    pub fn current_volume(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.current_volume = Some(value);
+        self
+    }
+    pub fn pressure(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.pressure = Some(value);
+        self
+    }
+    pub fn temperature(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.temperature = Some(value);
+        self
+    }
+    pub fn viscosity(mut self, value: V1NumberValue) -> V1TankBuilder {
+        self.viscosity = Some(value);
+        self
+    }
+    pub fn extinguishant(mut self, value: V1StringValue) -> V1TankBuilder {
+        self.extinguishant = Some(value);
+        self
+    }
+    pub fn build(self) -> V1Tank {
+        V1Tank {
+            name: self.name,
+            type_: self.type_,
+            capacity: self.capacity,
+            current_level: self.current_level,
+            current_volume: self.current_volume,
+            pressure: self.pressure,
+            temperature: self.temperature,
+            viscosity: self.viscosity,
+            extinguishant: self.extinguishant,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tanks_tests {
+    use serde_json::json;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::tanks::{V1Tank, V1Tanks};
+    use crate::{SignalKGetError, V1NumberValue, V1UpdateInfo, V1Vessel};
+
+    #[test]
+    fn tanks_sample() {
+        let file =
+            File::open("tests/specification/test_data/vessel-valid/tanks-sample.json").unwrap();
+        let vessel: V1Vessel = serde_json::from_reader(BufReader::new(file)).unwrap();
+        let tanks = vessel.tanks.clone().unwrap();
+        let fresh_water = &tanks.fresh_water.as_ref().unwrap()["main"];
+        assert_eq!(fresh_water.capacity.as_ref().unwrap().value, Some(31.7));
+        assert!(tanks.live_well.unwrap().contains_key("bait1"));
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["tanks", "freshWater", "main", "currentVolume"]),
+            Ok(0.0887)
+        );
+    }
+
+    #[test]
+    fn update_tank_level() {
+        let mut tanks = V1Tanks::default();
+        let info = V1UpdateInfo::default();
+        tanks.update(&mut vec!["fuel", "0", "currentLevel"], &json!(0.75), &info);
+        tanks.update(&mut vec!["fuel", "0", "capacity"], &json!(0.2), &info);
+        tanks.update(&mut vec!["fuel", "0", "type"], &json!("diesel"), &info);
+        let expected = V1Tanks::builder()
+            .add_fuel(
+                "0".into(),
+                V1Tank::builder()
+                    .current_level(V1NumberValue::builder().value(0.75).build())
+                    .capacity(V1NumberValue::builder().value(0.2).build())
+                    .type_(crate::definitions::V1StringValue::Value("diesel".into()))
+                    .build(),
+            )
+            .build();
+        assert_eq!(tanks, expected);
+    }
+
+    #[test]
+    fn update_tank_from_object() {
+        let mut tanks = V1Tanks::default();
+        let info = V1UpdateInfo::default();
+        tanks.update(
+            &mut vec!["fuel", "0"],
+            &json!({"currentLevel": 0.75, "capacity": 0.2}),
+            &info,
+        );
+        let expected = V1Tanks::builder()
+            .add_fuel(
+                "0".into(),
+                V1Tank::builder()
+                    .current_level(V1NumberValue::builder().value(0.75).build())
+                    .capacity(V1NumberValue::builder().value(0.2).build())
+                    .build(),
+            )
+            .build();
+        assert_eq!(tanks, expected);
+    }
+
+    #[test]
+    fn tank_paths() {
+        let mut vessel = V1Vessel::default();
+        let info = V1UpdateInfo::default();
+        vessel.update(
+            &mut vec!["tanks", "blackWater", "aft", "currentLevel"],
+            &json!(0.4),
+            &info,
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["tanks", "blackWater", "aft", "currentLevel"]),
+            Ok(0.4)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["tanks", "blackWater", "fwd", "currentLevel"]),
+            Err(SignalKGetError::NoSuchPath)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["tanks", "fuel", "0", "currentLevel"]),
+            Err(SignalKGetError::ValueNotSet)
+        );
+        assert_eq!(
+            vessel.get_f64_for_path(&mut vec!["tanks", "blackWater", "aft", "type"]),
+            Err(SignalKGetError::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn tanks_round_trip() {
+        let tanks = V1Tanks::builder()
+            .add_fresh_water(
+                "main".into(),
+                V1Tank::builder()
+                    .current_volume(V1NumberValue::builder().value(0.1).build())
+                    .build(),
+            )
+            .build();
+        let json = serde_json::to_value(&tanks).unwrap();
+        let parsed: V1Tanks = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, tanks);
+    }
+}
@@ -1,4 +1,5 @@
 use log::debug;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -6,12 +7,20 @@ use crate::communication::V1Communication;
 use crate::design::V1Design;
 use crate::electrical::V1Electrical;
 use crate::environment::V1Environment;
-use crate::full::Updatable;
-use crate::helper_functions::{get_f64_value_for_path, get_path, Path};
+use crate::full::{Updatable, V1PathValue};
+use crate::helper_functions::{
+    apply_update_values, get_f64_value_for_path, get_json_value, get_path, get_typed_value,
+    json_as_optional_string, json_leaves, last_key_wins, remove_null_values, F64CompatiblePath,
+    Path,
+};
 use crate::notification::V1Notification;
 use crate::performance::V1Performance;
+use crate::registrations::V1Registrations;
+use crate::sails::V1Sails;
+use crate::sensors::V1Sensors;
 use crate::steering::V1Steering;
-use crate::{SignalKGetError, V1Navigation, V1Propulsion, V1UpdateType};
+use crate::tanks::V1Tanks;
+use crate::{SignalKGetError, V1Navigation, V1Propulsion, V1UpdateInfo, V1UpdateType};
 
 /// An object describing an individual vessel. It should be an object in vessels,
 /// named using MMSI or a UUID
@@ -42,7 +51,8 @@ pub struct V1Vessel {
     /// Navigation data including Position, Course to next WP information, etc.
     pub navigation: Option<V1Navigation>,
 
-    // pub registrations: Option<HashMap<String, V1Registration>>,
+    /// The various registrations of the vessel.
+    pub registrations: Option<V1Registrations>,
     pub communication: Option<V1Communication>,
     /// Environmental data measured locally including Depth, Wind, Temp, etc.
     pub environment: Option<V1Environment>,
@@ -51,12 +61,16 @@ pub struct V1Vessel {
     pub electrical: Option<V1Electrical>,
 
     /// Notifications currently raised. Major categories have well-defined names, but the tree can be extended by any hierarchical structure
-    // pub notifications: Option<V1Notification>,
+    pub notifications: Option<V1Notification>,
     pub steering: Option<V1Steering>,
-    // pub tanks: Option<V1Tanks>,
+    /// Tank data, each tank indentified by a unique name i.e. FreshWater_2
+    #[serde(default, deserialize_with = "last_key_wins")]
+    pub tanks: Option<V1Tanks>,
     pub design: Option<V1Design>,
-    // pub sails: Option<V1Sails>,
-    // pub sensors: Option<V1Sensors>,
+    /// Sails data
+    pub sails: Option<V1Sails>,
+    /// Sensors, their state, and data
+    pub sensors: Option<V1Sensors>,
     pub performance: Option<V1Performance>,
     /// Engine data, each engine identified by a unique name i.e. Port_Engine
     pub propulsion: Option<HashMap<String, V1Propulsion>>,
@@ -73,33 +87,47 @@ impl Path<f64> for V1Vessel {
             "name" => Err(SignalKGetError::WrongDataType),
             "port" => Err(SignalKGetError::WrongDataType),
             "flag" => Err(SignalKGetError::WrongDataType),
+            "registrations" => Err(SignalKGetError::WrongDataType),
             "navigation" => get_path(path, &(self.navigation.as_ref())),
-            "communication" => Err(SignalKGetError::TBD),
+            "communication" => Err(SignalKGetError::WrongDataType),
             "environment" => get_path(path, &(self.environment.as_ref())),
-            "notifications" => Err(SignalKGetError::TBD),
-            "electrical" => Err(SignalKGetError::TBD),
-            "steering" => Err(SignalKGetError::TBD),
-            "tanks" => Err(SignalKGetError::TBD),
-            "design" => Err(SignalKGetError::TBD),
-            "sails" => Err(SignalKGetError::TBD),
-            "sensors" => Err(SignalKGetError::TBD),
-            "performance" => Err(SignalKGetError::TBD),
-            "propulsion" => Err(SignalKGetError::TBD),
+            "notifications" => get_path(path, &self.notifications.as_ref()),
+            "electrical" => {
+                if let Some(ref electrical) = self.electrical {
+                    electrical.get_f64_for_path(&mut path[1..].to_vec())
+                } else {
+                    Err(SignalKGetError::ValueNotSet)
+                }
+            }
+            "steering" => get_f64_value_for_path(&mut path.to_vec(), &self.steering),
+            "tanks" => get_f64_value_for_path(&mut path.to_vec(), &self.tanks),
+            "design" => get_f64_value_for_path(&mut path.to_vec(), &self.design),
+            "sails" => get_f64_value_for_path(&mut path.to_vec(), &self.sails),
+            "sensors" => get_f64_value_for_path(&mut path.to_vec(), &self.sensors),
+            "performance" => get_f64_value_for_path(&mut path.to_vec(), &self.performance),
+            "propulsion" => self.get_propulsion_f64(path),
 
             &_ => Err(SignalKGetError::NoSuchPath),
         }
     }
 }
 
+impl V1Vessel {
+    /// Apply an update, asking `accept` for each value if it should replace
+    /// the current value of its path
+    ///
+    /// Values that are not accepted are only recorded under `values`.
+    pub fn apply_update_with<F>(&mut self, update: &V1UpdateType, accept: &mut F)
+    where
+        F: FnMut(&str, &serde_json::Value, &V1UpdateInfo) -> bool,
+    {
+        apply_update_values(self, update, accept, V1Vessel::update);
+    }
+}
+
 impl Updatable for V1Vessel {
     fn apply_update(&mut self, update: &V1UpdateType) {
-        log::debug!("Apply update: {:?}", update);
-        if let Some(ref values) = update.values {
-            for value in values.iter() {
-                let mut path: Vec<&str> = value.path.split('.').collect();
-                self.update(&mut path, &value.value);
-            }
-        }
+        self.apply_update_with(update, &mut |_, _, _| true);
     }
 
     fn id(&self) -> String {
@@ -134,53 +162,32 @@ impl V1Vessel {
             Self::default()
         }
     }
-    pub fn update(&mut self, path: &mut Vec<&str>, value: &serde_json::value::Value) {
+    pub fn update(
+        &mut self,
+        path: &mut Vec<&str>,
+        value: &serde_json::value::Value,
+        info: &V1UpdateInfo,
+    ) {
         if path.is_empty() {
             return;
         }
         match path[0] {
-            "mmsi" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.mmsi = Some(string.to_string());
-                }
-            }
-            "url" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.url = Some(string.to_string());
-                }
-            }
-            "uuid" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.uuid = Some(string.to_string());
-                }
-            }
-            "mothership_mmsi" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.mothership_mmsi = Some(string.to_string());
-                }
-            }
-            "name" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.name = Some(string.to_string());
-                }
-            }
-            "port" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.port = Some(string.to_string());
-                }
-            }
-            "flag" => {
-                if let serde_json::Value::String(ref string) = value {
-                    self.flag = Some(string.to_string());
-                }
-            }
+            "mmsi" | "url" | "uuid" | "mothershipMmsi" | "name" | "port" | "flag"
+                if info.keeps_value() => {}
+            "mmsi" => self.mmsi = json_as_optional_string(value),
+            "url" => self.url = json_as_optional_string(value),
+            "uuid" => self.uuid = json_as_optional_string(value),
+            "mothershipMmsi" => self.mothership_mmsi = json_as_optional_string(value),
+            "name" => self.name = json_as_optional_string(value),
+            "port" => self.port = json_as_optional_string(value),
+            "flag" => self.flag = json_as_optional_string(value),
             "navigation" => {
                 if self.navigation.is_none() {
                     self.navigation = Some(V1Navigation::default());
                 }
                 if let Some(ref mut navigation) = self.navigation {
                     path.remove(0);
-                    navigation.update(path, value);
+                    navigation.update(path, value, info);
                 }
             }
             "environment" => {
@@ -189,7 +196,7 @@ impl V1Vessel {
                 }
                 if let Some(ref mut environment) = self.environment {
                     path.remove(0);
-                    environment.update(path, value);
+                    environment.update(path, value, info);
                 }
             }
             "electrical" => {
@@ -198,7 +205,16 @@ impl V1Vessel {
                 }
                 if let Some(ref mut electrical) = self.electrical {
                     path.remove(0);
-                    electrical.update(path, value);
+                    electrical.update(path, value, info);
+                }
+            }
+            "registrations" => {
+                if self.registrations.is_none() {
+                    self.registrations = Some(V1Registrations::default());
+                }
+                if let Some(ref mut registrations) = self.registrations {
+                    path.remove(0);
+                    registrations.update(path, value, info);
                 }
             }
             "communication" => {
@@ -207,7 +223,7 @@ impl V1Vessel {
                 }
                 if let Some(ref mut communication) = self.communication {
                     path.remove(0);
-                    communication.update(path, value);
+                    communication.update(path, value, info);
                 }
             }
             "design" => {
@@ -216,7 +232,7 @@ impl V1Vessel {
                 }
                 if let Some(ref mut design) = self.design {
                     path.remove(0);
-                    design.update(path, value);
+                    design.update(path, value, info);
                 }
             }
             "performance" => {
@@ -225,7 +241,63 @@ impl V1Vessel {
                 }
                 if let Some(ref mut performance) = self.performance {
                     path.remove(0);
-                    performance.update(path, value);
+                    performance.update(path, value, info);
+                }
+            }
+            "tanks" => {
+                if self.tanks.is_none() {
+                    self.tanks = Some(V1Tanks::default());
+                }
+                if let Some(ref mut tanks) = self.tanks {
+                    path.remove(0);
+                    tanks.update(path, value, info);
+                }
+            }
+            "sails" => {
+                if self.sails.is_none() {
+                    self.sails = Some(V1Sails::default());
+                }
+                if let Some(ref mut sails) = self.sails {
+                    path.remove(0);
+                    sails.update(path, value, info);
+                }
+            }
+            "sensors" => {
+                if self.sensors.is_none() {
+                    self.sensors = Some(V1Sensors::default());
+                }
+                if let Some(ref mut sensors) = self.sensors {
+                    path.remove(0);
+                    sensors.update(path, value, info);
+                }
+            }
+            "propulsion" => {
+                if path.len() < 2 {
+                    log::warn!("V1Vessel: Unknown update pattern: {:?}::{:?}", path, value);
+                    return;
+                }
+                let engine = self
+                    .propulsion
+                    .get_or_insert_with(HashMap::new)
+                    .entry(path[1].to_string())
+                    .or_default();
+                path.remove(0);
+                path.remove(0);
+                if path.is_empty() {
+                    path.push("");
+                }
+                engine.update(path, value, info);
+            }
+            "notifications" => {
+                if self.notifications.is_none() {
+                    self.notifications = Some(V1Notification::default());
+                }
+                if let Some(ref mut notifications) = self.notifications {
+                    path.remove(0);
+                    notifications.update(path, value, info);
+                    if notifications.is_empty() {
+                        self.notifications = None;
+                    }
                 }
             }
             "" => {
@@ -234,7 +306,7 @@ impl V1Vessel {
                     for (k, v) in map.iter() {
                         log::debug!(" key: {:?} value: {:?}", k, v);
                         let mut path = vec![k.as_str()];
-                        self.update(&mut path, v);
+                        self.update(&mut path, v, info);
                     }
                 }
             }
@@ -250,11 +322,13 @@ impl V1Vessel {
             "mmsi" => Err(SignalKGetError::WrongDataType),
             "url" => Err(SignalKGetError::WrongDataType),
             "uuid" => Err(SignalKGetError::WrongDataType),
-            "mothership_mmsi" => Err(SignalKGetError::WrongDataType),
+            "mothershipMmsi" => Err(SignalKGetError::WrongDataType),
             "name" => Err(SignalKGetError::WrongDataType),
             "port" => Err(SignalKGetError::WrongDataType),
             "flag" => Err(SignalKGetError::WrongDataType),
+            "registrations" => Err(SignalKGetError::WrongDataType),
             "navigation" => get_path(path, &self.navigation.as_ref()),
+            "communication" => Err(SignalKGetError::WrongDataType),
             "environment" => {
                 if let Some(ref environment) = self.environment {
                     path.remove(0);
@@ -272,18 +346,105 @@ impl V1Vessel {
                     Err(SignalKGetError::NoSuchPath)
                 }
             }
-            "notifications" => Err(SignalKGetError::TBD),
+            "notifications" => get_path(path, &self.notifications.as_ref()),
             "steering" => get_f64_value_for_path(path, &self.steering),
-            "tanks" => Err(SignalKGetError::TBD),
-            "design" => Err(SignalKGetError::TBD),
-            "sails" => Err(SignalKGetError::TBD),
-            "sensors" => Err(SignalKGetError::TBD),
+            "tanks" => get_f64_value_for_path(path, &self.tanks),
+            "design" => get_f64_value_for_path(path, &self.design),
+            "sails" => get_f64_value_for_path(path, &self.sails),
+            "sensors" => get_f64_value_for_path(path, &self.sensors),
             "performance" => get_f64_value_for_path(path, &self.performance),
-            "propulsion" => Err(SignalKGetError::TBD),
+            "propulsion" => self.get_propulsion_f64(path),
             &_ => Err(SignalKGetError::NoSuchPath),
         }
         // Ok(5.1)
     }
+
+    /// Look up `propulsion.<engine>.<field>`, engines are named by the vessel
+    fn get_propulsion_f64(&self, path: &[&str]) -> Result<f64, SignalKGetError> {
+        if path.len() < 3 {
+            return Err(SignalKGetError::WrongDataType);
+        }
+        if let Some(engine) = self
+            .propulsion
+            .as_ref()
+            .and_then(|propulsion| propulsion.get(path[1]))
+        {
+            engine.get_f64_for_path(&mut path[2..].to_vec())
+        } else {
+            Err(SignalKGetError::NoSuchPath)
+        }
+    }
+
+    /// Return the value stored for a path in this vessel as any deserializable type
+    ///
+    /// Besides `f64` this can read strings, booleans, enums, positions and whole
+    /// objects, e.g. `name`, `navigation.position` or `steering.autopilot.state`.
+    pub fn get_for_path<T: DeserializeOwned + PartialEq>(
+        &self,
+        path: &[&str],
+    ) -> Result<T, SignalKGetError> {
+        debug!("get_for_path({:?})", path);
+        let (json, rest) = self.group_json(path)?;
+        get_typed_value(get_json_value(&json, rest)?)
+    }
+
+    /// Iterate over every populated leaf in this vessel
+    ///
+    /// Leaves are values with their timestamp and `$source`, the paths are the
+    /// same as the ones used by `update`, e.g. `navigation.speedOverGround` or
+    /// `propulsion.port.revolutions`.
+    pub fn leaves(&self) -> std::vec::IntoIter<V1PathValue> {
+        json_leaves(self)
+    }
+
+    /// Return the JSON subtree stored for a path in this vessel
+    ///
+    /// Fields that are not set are left out of the result.
+    pub fn get_json_for_path(&self, path: &[&str]) -> Result<serde_json::Value, SignalKGetError> {
+        debug!("get_json_for_path({:?})", path);
+        let (json, rest) = self.group_json(path)?;
+        let mut subtree = get_json_value(&json, rest)?.clone();
+        remove_null_values(&mut subtree);
+        Ok(subtree)
+    }
+
+    /// Serialize only the group a path starts in, e.g. `navigation`, and
+    /// return it with the rest of the path
+    ///
+    /// An empty path addresses the whole vessel.
+    fn group_json<'a, 'b>(
+        &self,
+        path: &'a [&'b str],
+    ) -> Result<(serde_json::Value, &'a [&'b str]), SignalKGetError> {
+        let Some((group, rest)) = path.split_first() else {
+            let json = serde_json::to_value(self).map_err(|_| SignalKGetError::WrongDataType)?;
+            return Ok((json, path));
+        };
+        let json = match *group {
+            "mmsi" => serde_json::to_value(&self.mmsi),
+            "url" => serde_json::to_value(&self.url),
+            "uuid" => serde_json::to_value(&self.uuid),
+            "mothershipMmsi" => serde_json::to_value(&self.mothership_mmsi),
+            "name" => serde_json::to_value(&self.name),
+            "port" => serde_json::to_value(&self.port),
+            "flag" => serde_json::to_value(&self.flag),
+            "navigation" => serde_json::to_value(&self.navigation),
+            "registrations" => serde_json::to_value(&self.registrations),
+            "communication" => serde_json::to_value(&self.communication),
+            "environment" => serde_json::to_value(&self.environment),
+            "electrical" => serde_json::to_value(&self.electrical),
+            "notifications" => serde_json::to_value(&self.notifications),
+            "steering" => serde_json::to_value(&self.steering),
+            "tanks" => serde_json::to_value(&self.tanks),
+            "design" => serde_json::to_value(&self.design),
+            "sails" => serde_json::to_value(&self.sails),
+            "sensors" => serde_json::to_value(&self.sensors),
+            "performance" => serde_json::to_value(&self.performance),
+            "propulsion" => serde_json::to_value(&self.propulsion),
+            &_ => return Err(SignalKGetError::NoSuchPath),
+        };
+        Ok((json.map_err(|_| SignalKGetError::WrongDataType)?, rest))
+    }
 }
 
 #[derive(Default)]
@@ -296,13 +457,17 @@ pub struct V1VesselBuilder {
     flag: Option<String>,
     port: Option<String>,
     navigation: Option<V1Navigation>,
+    registrations: Option<V1Registrations>,
     communication: Option<V1Communication>,
     environment: Option<V1Environment>,
     electrical: Option<V1Electrical>,
     notifications: Option<V1Notification>,
+    tanks: Option<V1Tanks>,
     propulsion: Option<HashMap<String, V1Propulsion>>,
     steering: Option<V1Steering>,
     design: Option<V1Design>,
+    sails: Option<V1Sails>,
+    sensors: Option<V1Sensors>,
     performance: Option<V1Performance>,
 }
 
@@ -339,6 +504,10 @@ impl V1VesselBuilder {
         self.navigation = Some(value);
         self
     }
+    pub fn registrations(mut self, value: V1Registrations) -> V1VesselBuilder {
+        self.registrations = Some(value);
+        self
+    }
     pub fn communication(mut self, value: V1Communication) -> V1VesselBuilder {
         self.communication = Some(value);
         self
@@ -355,10 +524,22 @@ impl V1VesselBuilder {
         self.notifications = Some(value);
         self
     }
+    pub fn tanks(mut self, value: V1Tanks) -> V1VesselBuilder {
+        self.tanks = Some(value);
+        self
+    }
     pub fn design(mut self, value: V1Design) -> V1VesselBuilder {
         self.design = Some(value);
         self
     }
+    pub fn sails(mut self, value: V1Sails) -> V1VesselBuilder {
+        self.sails = Some(value);
+        self
+    }
+    pub fn sensors(mut self, value: V1Sensors) -> V1VesselBuilder {
+        self.sensors = Some(value);
+        self
+    }
     pub fn add_propulsion(mut self, key: String, value: V1Propulsion) -> V1VesselBuilder {
         if self.propulsion.is_none() {
             self.propulsion = Some(HashMap::new());
@@ -376,12 +557,16 @@ impl V1VesselBuilder {
             port: self.port,
             flag: self.flag,
             navigation: self.navigation,
+            registrations: self.registrations,
             communication: self.communication,
             environment: self.environment,
             electrical: self.electrical,
-            // notifications: self.notifications,
+            notifications: self.notifications,
+            tanks: self.tanks,
             steering: self.steering,
             design: self.design,
+            sails: self.sails,
+            sensors: self.sensors,
             propulsion: self.propulsion,
             url: self.url,
             mothership_mmsi: self.mothership_mmsi,
@@ -395,7 +580,9 @@ mod context_tests {
     use serde_json::{Number, Value};
 
     use crate::full::Updatable;
-    use crate::{V1Navigation, V1NumberValue, V1UpdateType, V1UpdateValue, V1Vessel};
+    use crate::{
+        V1Meta, V1Navigation, V1NumberValue, V1UpdateMeta, V1UpdateType, V1UpdateValue, V1Vessel,
+    };
 
     #[test]
     fn update_navigation_sog_12_6_in_existing_tree() {
@@ -511,4 +698,31 @@ mod context_tests {
         let vessel = V1Vessel::new_with_id("urn:mrn:imo:mmsi:366982330");
         assert_eq!(vessel.mmsi, Some("366982330".to_string()));
     }
+
+    #[test]
+    fn meta_is_attached_without_touching_values() {
+        let mut vessel = V1Vessel::builder()
+            .name("Motu".into())
+            .navigation(
+                V1Navigation::builder()
+                    .speed_over_ground(V1NumberValue::builder().value(3.85).build())
+                    .build(),
+            )
+            .build();
+        let meta = V1Meta::builder().units("m/s".into()).build();
+        let update = V1UpdateType::builder()
+            .meta(V1UpdateMeta::new(
+                "navigation.speedOverGround".into(),
+                meta.clone(),
+            ))
+            .meta(V1UpdateMeta::new("name".into(), meta.clone()))
+            .build();
+
+        vessel.apply_update(&update);
+
+        assert_eq!(vessel.name, Some("Motu".to_string()));
+        let sog = vessel.navigation.unwrap().speed_over_ground.unwrap();
+        assert_eq!(sog.value, Some(3.85));
+        assert_eq!(sog.meta, Some(Box::new(meta)));
+    }
 }
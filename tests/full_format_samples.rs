@@ -2,12 +2,13 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
+use signalk::definitions::V1MetaDisplayScale;
 use signalk::full::V1FullFormat;
 use signalk::{
-    V1ACBus, V1CommonValueFields, V1Electrical, V1ElectricalACQualities, V1ElectricalIdentity,
-    V1Environment, V1EnvironmentDepth, V1EnvironmentTime, V1Navigation, V1Notification,
-    V1NotificationValue, V1NumberValue, V1PositionType, V1PositionValue, V1Propulsion, V1Source,
-    V1SourceProperty, V1Sources, V1Vessel,
+    SignalKGetError, V1ACBus, V1CommonValueFields, V1Electrical, V1ElectricalACQualities,
+    V1ElectricalIdentity, V1Environment, V1EnvironmentDepth, V1EnvironmentTime, V1Meta, V1MetaZone,
+    V1Navigation, V1Notification, V1NotificationValue, V1NumberValue, V1PositionType,
+    V1PositionValue, V1Propulsion, V1Source, V1SourceProperty, V1SourceValue, V1Sources, V1Vessel,
 };
 
 fn read_signalk_from_file(path: PathBuf) -> V1FullFormat {
@@ -176,6 +177,44 @@ fn test_sample_full_0183_rmc_full() {
                                 .value(245.69)
                                 .timestamp("2015-03-06T16:57:53.643Z".into())
                                 .source("sources.gps_0183_RMC".into())
+                                .meta(
+                                    V1Meta::builder()
+                                        .description("Course over ground (true)".into())
+                                        .units("rad".into())
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(260.0)
+                                                .upper(360.0)
+                                                .state("alarm".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(220.0)
+                                                .upper(230.0)
+                                                .state("warn".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(0.0)
+                                                .upper(220.0)
+                                                .state("alarm".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(220.0)
+                                                .upper(260.0)
+                                                .state("normal".into())
+                                                .build(),
+                                        )
+                                        .short_name("COG".into())
+                                        .alarm_method("sound".into())
+                                        .warn_method("visual".into())
+                                        .display_name("COG (True)".into())
+                                        .build(),
+                                )
                                 .build(),
                         )
                         .position(
@@ -284,6 +323,58 @@ fn test_sample_docs_data_model_metadata() {
                                 .value(1280.0)
                                 .timestamp("2014-08-15T19:00:15.402Z".into())
                                 .source("foo.bar".into())
+                                .meta(
+                                    V1Meta::builder()
+                                        .display_name("Port Tachometer".into())
+                                        .long_name("Engine 2 Tachometer".into())
+                                        .short_name("Tacho".into())
+                                        .description("Engine revolutions (x60 for RPM)".into())
+                                        .units("Hz".into())
+                                        .timeout(1.0)
+                                        .display_scale(
+                                            V1MetaDisplayScale::builder()
+                                                .lower(0.0)
+                                                .upper(75.0)
+                                                .type_("linear".into())
+                                                .build(),
+                                        )
+                                        .alert_method("visual".into())
+                                        .warn_method("visual".into())
+                                        .alarm_method("sound".into())
+                                        .alarm_method("visual".into())
+                                        .emergency_method("sound".into())
+                                        .emergency_method("visual".into())
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .upper(4.0)
+                                                .state("alarm".into())
+                                                .message("Stopped or very slow".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(4.0)
+                                                .upper(60.0)
+                                                .state("normal".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(60.0)
+                                                .upper(65.0)
+                                                .state("warn".into())
+                                                .message("Approaching maximum".into())
+                                                .build(),
+                                        )
+                                        .zones(
+                                            V1MetaZone::builder()
+                                                .lower(65.0)
+                                                .state("alarm".into())
+                                                .message("Exceeding maximum".into())
+                                                .build(),
+                                        )
+                                        .build(),
+                                )
                                 .build(),
                         )
                         .build(),
@@ -312,6 +403,22 @@ fn test_sample_docs_data_model_multiple_values_metadata() {
                                 .value(3.61562407843144)
                                 .timestamp("2017-04-03T06:14:04.451Z".into())
                                 .source("ttyUSB0.GP".into())
+                                .add_source_value(
+                                    "ttyUSB0.GP.RMC".into(),
+                                    V1SourceValue {
+                                        value: Some(3.61562407843144),
+                                        timestamp: Some("2017-04-03T06:14:04.451Z".into()),
+                                        ..Default::default()
+                                    },
+                                )
+                                .add_source_value(
+                                    "n2k.ikommunicate.128267".into(),
+                                    V1SourceValue {
+                                        value: Some(3.615624078431453),
+                                        timestamp: Some("2017-04-03T06:14:04.451Z".into()),
+                                        ..Default::default()
+                                    },
+                                )
                                 .build(),
                         )
                         .build(),
@@ -410,6 +517,39 @@ fn test_sample_docs_notifications() {
     assert_eq!(sk_data, expected);
 }
 
+#[test]
+fn test_sample_docs_notifications_by_state() {
+    let folder = Path::new("tests/specification/examples/full/");
+    let sk_data = read_signalk_from_file(folder.join("docs-notifications.json"));
+    let paths = |state: Option<&str>| -> Vec<String> {
+        sk_data
+            .get_notifications("self", state)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    };
+    assert_eq!(
+        paths(None),
+        vec!["mob", "navigation.anchor.currentRadius", "navigation.gnss"]
+    );
+    assert_eq!(paths(Some("emergency")), vec!["mob"]);
+    assert_eq!(
+        paths(Some("alarm")),
+        vec!["navigation.anchor.currentRadius"]
+    );
+    assert_eq!(paths(Some("alert")), vec!["navigation.gnss"]);
+    assert!(paths(Some("warn")).is_empty());
+    assert_eq!(
+        sk_data.get_f64_for_path("self.notifications.mob".into()),
+        Err(SignalKGetError::WrongDataType)
+    );
+    assert_eq!(
+        sk_data.get_f64_for_path("self.notifications.fire".into()),
+        Err(SignalKGetError::NoSuchPath)
+    );
+}
+
 #[test]
 fn test_sample_electrical_full() {
     let expected = V1FullFormat::builder()
@@ -820,6 +960,39 @@ fn test_sample_depth_meta_attr() {
                                         .value(3.4)
                                         .timestamp("2015-03-06T16:57:53.643Z".into())
                                         .source("nmea1.II".into())
+                                        .meta(
+                                            V1Meta::builder()
+                                                .description("Depth below keel".into())
+                                                .display_name("Depth Below Keel".into())
+                                                .long_name("Depth Below Keel".into())
+                                                .short_name("DBK".into())
+                                                .units("Meters (m)".into())
+                                                .warn_method("visual".into())
+                                                .alarm_method("sound".into())
+                                                .zones(
+                                                    V1MetaZone::builder()
+                                                        .lower(0.0)
+                                                        .upper(1.5)
+                                                        .state("alarm".into())
+                                                        .message("Running aground!".into())
+                                                        .build(),
+                                                )
+                                                .zones(
+                                                    V1MetaZone::builder()
+                                                        .lower(1.5)
+                                                        .upper(3.0)
+                                                        .state("warn".into())
+                                                        .message("Shallow water!".into())
+                                                        .build(),
+                                                )
+                                                .zones(
+                                                    V1MetaZone::builder()
+                                                        .lower(3.0)
+                                                        .state("normal".into())
+                                                        .build(),
+                                                )
+                                                .build(),
+                                        )
                                         .build(),
                                 )
                                 .build(),
@@ -0,0 +1,56 @@
+use signalk::V1Auth;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+#[test]
+#[should_panic]
+fn login_request_no_pass() {
+    let path = Path::new("tests/specification/test_data/auth-invalid/login-request-no-pass.json");
+    let file = File::open(path).unwrap();
+    let reader = BufReader::new(file);
+    let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
+    println!("{:?}", sk_data);
+}
+
+#[test]
+#[should_panic]
+fn login_request_no_requestid() {
+    let path =
+        Path::new("tests/specification/test_data/auth-invalid/login-request-no-requestid.json");
+    let file = File::open(path).unwrap();
+    let reader = BufReader::new(file);
+    let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
+    println!("{:?}", sk_data);
+}
+
+#[test]
+#[should_panic]
+fn login_request_no_username() {
+    let path =
+        Path::new("tests/specification/test_data/auth-invalid/login-request-no-username.json");
+    let file = File::open(path).unwrap();
+    let reader = BufReader::new(file);
+    let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
+    println!("{:?}", sk_data);
+}
+
+#[test]
+#[should_panic]
+fn login_response_no_token() {
+    let path = Path::new("tests/specification/test_data/auth-invalid/login-response-no-token.json");
+    let file = File::open(path).unwrap();
+    let reader = BufReader::new(file);
+    let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
+    println!("{:?}", sk_data);
+}
+
+#[test]
+#[should_panic]
+fn validate_logout_request() {
+    let path = Path::new("tests/specification/test_data/auth-invalid/validate-logout-request.json");
+    let file = File::open(path).unwrap();
+    let reader = BufReader::new(file);
+    let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
+    println!("{:?}", sk_data);
+}
@@ -1,6 +1,9 @@
-/* TODO: Auth messages not supported
+use signalk::V1Auth;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
 #[test]
-#[ignore = "not yet implemented"]
 fn login_request() {
     let path = Path::new("tests/specification/test_data/auth-valid/login-request.json");
     let file = File::open(path).unwrap();
@@ -44,4 +47,3 @@ fn validate_response() {
     let sk_data: V1Auth = serde_json::from_reader(reader).unwrap();
     println!("{:?}", sk_data);
 }
-*/
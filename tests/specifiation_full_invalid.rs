@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
-// TODO: Aircraft not supported
+// TODO: Aircraft MMSI are not validated
 /*
 #[test]
 #[should_panic]
@@ -143,7 +143,7 @@ fn alarms_must_use_array_of_states_not_string() {
 }
 */
 
-// TODO: Aton not supported
+// TODO: Aton types, MMSI and members are not validated
 /*
 #[test]
 #[should_panic]
@@ -243,8 +243,6 @@ fn invalid_timestamp_subtle() {
 }
 */
 
-// TODO: Navigation Maneuver not supported
-/*
 #[test]
 #[should_panic]
 fn maneuver_bad() {
@@ -254,7 +252,6 @@ fn maneuver_bad() {
     let sk_data: V1FullFormat = serde_json::from_reader(reader).unwrap();
     println!("{:?}", sk_data);
 }
-*/
 
 // MMSI not verified
 /*
@@ -269,8 +266,6 @@ fn mothership_bad() {
 }
 */
 
-// TODO: Navigation destination not supported
-/*
 #[test]
 #[should_panic]
 fn nav_destination_bad() {
@@ -280,9 +275,8 @@ fn nav_destination_bad() {
     let sk_data: V1FullFormat = serde_json::from_reader(reader).unwrap();
     println!("{:?}", sk_data);
 }
-*/
 
-// TODO: Sar not supported
+// TODO: Sar MMSI and members are not validated
 /*
 
 #[test]